@@ -0,0 +1,217 @@
+mod common;
+
+// Only used by the backend groups, each behind its feature
+#[cfg(any(
+    feature = "erasure",
+    feature = "novelpoly",
+    feature = "rs16",
+    feature = "simd"
+))]
+use common::{generate_data, supported_configs, BenchConfig};
+#[cfg(any(
+    feature = "erasure",
+    feature = "novelpoly",
+    feature = "rs16",
+    feature = "simd"
+))]
+use divan::{black_box, Bencher};
+#[cfg(any(
+    feature = "erasure",
+    feature = "novelpoly",
+    feature = "rs16",
+    feature = "simd"
+))]
+use reed_solomon_benchmark::codec;
+#[cfg(any(
+    feature = "erasure",
+    feature = "novelpoly",
+    feature = "rs16",
+    feature = "simd"
+))]
+use reed_solomon_benchmark::commitment::MerkleTree;
+
+fn main() {
+    divan::main();
+}
+
+/// Times encoding, to read the commitment costs against.
+#[cfg(any(
+    feature = "erasure",
+    feature = "novelpoly",
+    feature = "rs16",
+    feature = "simd"
+))]
+fn bench_encode(bencher: Bencher, backend: &str, config: BenchConfig) {
+    let codec = codec::backend(backend).expect("bench group for a compiled-in backend");
+    let data = generate_data(config.data_size);
+    let (k, m) = (config.data_shards(), config.coding_shards());
+
+    bencher.bench_local(|| {
+        black_box(codec.encode(k, m, &data).unwrap());
+    });
+}
+
+/// Times building the Merkle tree over all `total_shards()` encoded shards.
+#[cfg(any(
+    feature = "erasure",
+    feature = "novelpoly",
+    feature = "rs16",
+    feature = "simd"
+))]
+fn bench_commit(bencher: Bencher, backend: &str, config: BenchConfig) {
+    let codec = codec::backend(backend).expect("bench group for a compiled-in backend");
+    let data = generate_data(config.data_size);
+    let shards = codec
+        .encode(config.data_shards(), config.coding_shards(), &data)
+        .unwrap();
+
+    bencher.bench_local(|| {
+        black_box(MerkleTree::new(&shards).root());
+    });
+}
+
+/// Times checking one shard's inclusion proof, as every receiver does per shard.
+#[cfg(any(
+    feature = "erasure",
+    feature = "novelpoly",
+    feature = "rs16",
+    feature = "simd"
+))]
+fn bench_verify_proof(bencher: Bencher, backend: &str, config: BenchConfig) {
+    let codec = codec::backend(backend).expect("bench group for a compiled-in backend");
+    let data = generate_data(config.data_size);
+    let shards = codec
+        .encode(config.data_shards(), config.coding_shards(), &data)
+        .unwrap();
+    let tree = MerkleTree::new(&shards);
+    let (root, proof) = (tree.root(), tree.proof(0).unwrap());
+
+    bencher.bench_local(|| {
+        assert!(black_box(proof.verify(&root, shards.len(), &shards[0])));
+    });
+}
+
+// ============================================================================
+// reed-solomon-erasure benchmarks
+// ============================================================================
+
+#[cfg(feature = "erasure")]
+#[divan::bench_group(name = "commit_erasure")]
+mod commit_erasure {
+    use super::*;
+
+    #[divan::bench(args = supported_configs("erasure"))]
+    fn encode(bencher: Bencher, config: BenchConfig) {
+        bench_encode(bencher, "erasure", config);
+    }
+
+    #[divan::bench(args = supported_configs("erasure"))]
+    fn commit(bencher: Bencher, config: BenchConfig) {
+        bench_commit(bencher, "erasure", config);
+    }
+
+    #[divan::bench(args = supported_configs("erasure"))]
+    fn verify_proof(bencher: Bencher, config: BenchConfig) {
+        bench_verify_proof(bencher, "erasure", config);
+    }
+}
+
+// ============================================================================
+// reed-solomon-erasure GF(2^16) benchmarks
+// ============================================================================
+
+#[cfg(feature = "erasure")]
+#[divan::bench_group(name = "commit_erasure16")]
+mod commit_erasure16 {
+    use super::*;
+
+    #[divan::bench(args = supported_configs("erasure16"))]
+    fn encode(bencher: Bencher, config: BenchConfig) {
+        bench_encode(bencher, "erasure16", config);
+    }
+
+    #[divan::bench(args = supported_configs("erasure16"))]
+    fn commit(bencher: Bencher, config: BenchConfig) {
+        bench_commit(bencher, "erasure16", config);
+    }
+
+    #[divan::bench(args = supported_configs("erasure16"))]
+    fn verify_proof(bencher: Bencher, config: BenchConfig) {
+        bench_verify_proof(bencher, "erasure16", config);
+    }
+}
+
+// ============================================================================
+// reed-solomon-novelpoly benchmarks
+// ============================================================================
+
+#[cfg(feature = "novelpoly")]
+#[divan::bench_group(name = "commit_novelpoly")]
+mod commit_novelpoly {
+    use super::*;
+
+    #[divan::bench(args = supported_configs("novelpoly"))]
+    fn encode(bencher: Bencher, config: BenchConfig) {
+        bench_encode(bencher, "novelpoly", config);
+    }
+
+    #[divan::bench(args = supported_configs("novelpoly"))]
+    fn commit(bencher: Bencher, config: BenchConfig) {
+        bench_commit(bencher, "novelpoly", config);
+    }
+
+    #[divan::bench(args = supported_configs("novelpoly"))]
+    fn verify_proof(bencher: Bencher, config: BenchConfig) {
+        bench_verify_proof(bencher, "novelpoly", config);
+    }
+}
+
+// ============================================================================
+// reed-solomon-16 benchmarks
+// ============================================================================
+
+#[cfg(feature = "rs16")]
+#[divan::bench_group(name = "commit_rs16")]
+mod commit_rs16 {
+    use super::*;
+
+    #[divan::bench(args = supported_configs("rs16"))]
+    fn encode(bencher: Bencher, config: BenchConfig) {
+        bench_encode(bencher, "rs16", config);
+    }
+
+    #[divan::bench(args = supported_configs("rs16"))]
+    fn commit(bencher: Bencher, config: BenchConfig) {
+        bench_commit(bencher, "rs16", config);
+    }
+
+    #[divan::bench(args = supported_configs("rs16"))]
+    fn verify_proof(bencher: Bencher, config: BenchConfig) {
+        bench_verify_proof(bencher, "rs16", config);
+    }
+}
+
+// ============================================================================
+// reed-solomon-simd benchmarks
+// ============================================================================
+
+#[cfg(feature = "simd")]
+#[divan::bench_group(name = "commit_simd")]
+mod commit_simd {
+    use super::*;
+
+    #[divan::bench(args = supported_configs("simd"))]
+    fn encode(bencher: Bencher, config: BenchConfig) {
+        bench_encode(bencher, "simd", config);
+    }
+
+    #[divan::bench(args = supported_configs("simd"))]
+    fn commit(bencher: Bencher, config: BenchConfig) {
+        bench_commit(bencher, "simd", config);
+    }
+
+    #[divan::bench(args = supported_configs("simd"))]
+    fn verify_proof(bencher: Bencher, config: BenchConfig) {
+        bench_verify_proof(bencher, "simd", config);
+    }
+}
@@ -1,75 +1,89 @@
 /// Common utilities for Reed-Solomon benchmarks
 use std::collections::HashMap;
 
-/// Configuration for a Reed-Solomon test
-#[derive(Debug, Clone, Copy)]
-pub struct BenchConfig {
-    /// F value (data shards = F, coding shards = 2F)
-    pub f: usize,
-    /// Data size in bytes
-    pub data_size: usize,
-}
-
-impl BenchConfig {
-    pub fn new(f: usize, data_size: usize) -> Self {
-        Self { f, data_size }
-    }
-
-    pub fn data_shards(&self) -> usize {
-        self.f
-    }
+use reed_solomon_benchmark::codec;
+pub use reed_solomon_benchmark::config::{
+    all_configs, content_configs, format_size, large_configs, small_configs, unaligned_configs,
+    BenchConfig, SHARE_SIZE, SQUARE_WIDTHS,
+};
+pub use reed_solomon_benchmark::data::Content;
+use reed_solomon_benchmark::data::DEFAULT_SEED;
+use reed_solomon_benchmark::square;
 
-    pub fn coding_shards(&self) -> usize {
-        2 * self.f
-    }
+/// Generate test data of specified size
+pub fn generate_data(size: usize) -> Vec<u8> {
+    // Use a simple pattern for reproducibility
+    generate_content(size, &Content::Ramp)
+}
 
-    pub fn total_shards(&self) -> usize {
-        3 * self.f
-    }
+/// Generate `size` bytes of `content` from the default seed
+pub fn generate_content(size: usize, content: &Content) -> Vec<u8> {
+    content
+        .generate(size, DEFAULT_SEED)
+        .expect("synthetic content never fails")
+}
 
-    pub fn shard_size(&self) -> usize {
-        let size: usize = (self.data_size + self.f - 1) / self.f;
-        // Round up to nearest multiple of 64
-        ((size + 63) / 64) * 64
-    }
+/// Benchmark configurations `backend` can run; the rest are reported and skipped
+///
+/// `backend` is an adapter name from `reed_solomon_benchmark::codec`.
+#[allow(dead_code)]
+pub fn supported_configs(backend: &str) -> Vec<BenchConfig> {
+    filter_supported(backend, all_configs())
 }
 
-/// All F values to test
-pub const F_VALUES: &[usize] = &[1, 2, 3, 5, 10, 20, 33, 66];
+/// Unaligned-payload configurations `backend` can run
+#[allow(dead_code)]
+pub fn supported_unaligned_configs(backend: &str) -> Vec<BenchConfig> {
+    filter_supported(backend, unaligned_configs())
+}
 
-/// All data sizes to test (in bytes)
-pub const DATA_SIZES: &[usize] = &[
-    256 * 1024, // 256KB
-    512 * 1024, // 512KB
-    1024 * 1024, // 1MB
+/// Large-committee configurations `backend` can run
+#[allow(dead_code)]
+pub fn supported_large_configs(backend: &str) -> Vec<BenchConfig> {
+    filter_supported(backend, large_configs())
+}
 
-                // 1024,              // 1KB
-                // 4 * 1024,        // 4KB
-                // 16 * 1024,       // 16KB
-                // 64 * 1024,       // 64KB
-                // 2 * 1024 * 1024, // 2MB
-                // 4 * 1024 * 1024, // 4MB
-                // 16 * 1024 * 1024,  // 16MB
-                // 64 * 1024 * 1024,  // 64MB
-                // 100 * 1024 * 1024, // 100MB
-];
+/// Small-message configurations `backend` can run
+#[allow(dead_code)]
+pub fn supported_small_configs(backend: &str) -> Vec<BenchConfig> {
+    filter_supported(backend, small_configs())
+}
 
-/// Generate test data of specified size
-pub fn generate_data(size: usize) -> Vec<u8> {
-    // Use a simple pattern for reproducibility
-    (0..size).map(|i| (i % 256) as u8).collect()
+/// Content-axis configurations `backend` can run, crossed with every synthetic content
+#[allow(dead_code)]
+pub fn supported_content_configs(backend: &str) -> Vec<(BenchConfig, Content)> {
+    filter_supported(backend, content_configs())
+        .into_iter()
+        .flat_map(|config| {
+            Content::SYNTHETIC
+                .iter()
+                .map(move |content| (config, content.clone()))
+        })
+        .collect()
 }
 
-/// Format data size for display
+/// Square widths `k` whose 2D extension `backend` can run
 #[allow(dead_code)]
-pub fn format_size(size: usize) -> String {
-    if size >= 1024 * 1024 {
-        format!("{}MB", size / (1024 * 1024))
-    } else if size >= 1024 {
-        format!("{}KB", size / 1024)
-    } else {
-        format!("{}B", size)
+pub fn supported_square_widths(backend: &str) -> Vec<usize> {
+    let codec = codec::backend(backend).expect("bench group for a compiled-in backend");
+    let (supported, unsupported): (Vec<_>, Vec<_>) = SQUARE_WIDTHS
+        .iter()
+        .partition(|&&k| square::supports(codec.as_ref(), k, SHARE_SIZE));
+    for k in unsupported {
+        eprintln!("unsupported: {}/k{}", backend, k);
     }
+    supported
+}
+
+fn filter_supported(backend: &str, configs: Vec<BenchConfig>) -> Vec<BenchConfig> {
+    let codec = codec::backend(backend).expect("bench group for a compiled-in backend");
+    let (supported, unsupported): (Vec<_>, Vec<_>) = configs
+        .into_iter()
+        .partition(|config| codec.supports(config.data_shards(), config.coding_shards()));
+    for config in unsupported {
+        eprintln!("unsupported: {}", bench_name(backend, &config));
+    }
+    supported
 }
 
 /// Generate a benchmark name
@@ -83,17 +97,6 @@ pub fn bench_name(crate_name: &str, config: &BenchConfig) -> String {
     )
 }
 
-/// Get all benchmark configurations
-pub fn all_configs() -> Vec<BenchConfig> {
-    let mut configs = Vec::new();
-    for &f in F_VALUES {
-        for &data_size in DATA_SIZES {
-            configs.push(BenchConfig::new(f, data_size));
-        }
-    }
-    configs
-}
-
 /// Cache for pre-generated test data
 #[allow(dead_code)]
 pub struct DataCache {
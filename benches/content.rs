@@ -0,0 +1,178 @@
+mod common;
+
+// Only used by the backend groups, each behind its feature
+#[cfg(any(
+    feature = "erasure",
+    feature = "novelpoly",
+    feature = "rs16",
+    feature = "simd"
+))]
+use common::{generate_content, supported_content_configs, BenchConfig, Content};
+#[cfg(any(
+    feature = "erasure",
+    feature = "novelpoly",
+    feature = "rs16",
+    feature = "simd"
+))]
+use divan::{black_box, Bencher};
+#[cfg(any(
+    feature = "erasure",
+    feature = "novelpoly",
+    feature = "rs16",
+    feature = "simd"
+))]
+use reed_solomon_benchmark::codec;
+
+fn main() {
+    divan::main();
+}
+
+// The other bench targets always code the `i % 256` ramp. These repeat a few
+// configurations once per synthetic content, through the library adapters so
+// every backend sees the same bytes; a backend much faster on `Zeros` than on
+// `Uniform` shortcuts zero blocks.
+
+/// Times encoding `content`.
+#[cfg(any(
+    feature = "erasure",
+    feature = "novelpoly",
+    feature = "rs16",
+    feature = "simd"
+))]
+fn bench_encode(bencher: Bencher, backend: &str, (config, content): &(BenchConfig, Content)) {
+    let codec = codec::backend(backend).expect("bench group for a compiled-in backend");
+    let data = generate_content(config.data_size, content);
+    let (k, m) = (config.data_shards(), config.coding_shards());
+
+    bencher.bench_local(|| {
+        black_box(codec.encode(k, m, &data).unwrap());
+    });
+}
+
+/// Times decoding `content` with the first F shards lost.
+#[cfg(any(
+    feature = "erasure",
+    feature = "novelpoly",
+    feature = "rs16",
+    feature = "simd"
+))]
+fn bench_decode_f_missing(
+    bencher: Bencher,
+    backend: &str,
+    (config, content): &(BenchConfig, Content),
+) {
+    let codec = codec::backend(backend).expect("bench group for a compiled-in backend");
+    let data = generate_content(config.data_size, content);
+    let (k, m) = (config.data_shards(), config.coding_shards());
+    let received: Vec<Option<Vec<u8>>> = codec
+        .encode(k, m, &data)
+        .unwrap()
+        .into_iter()
+        .enumerate()
+        .map(|(i, shard)| (i >= config.f).then_some(shard))
+        .collect();
+
+    bencher.bench_local(|| {
+        black_box(codec.decode(k, m, &received, data.len()).unwrap());
+    });
+}
+
+// ============================================================================
+// reed-solomon-erasure benchmarks
+// ============================================================================
+
+#[cfg(feature = "erasure")]
+#[divan::bench_group(name = "content_erasure")]
+mod content_erasure {
+    use super::*;
+
+    #[divan::bench(args = supported_content_configs("erasure"))]
+    fn encode(bencher: Bencher, args: &(BenchConfig, Content)) {
+        bench_encode(bencher, "erasure", args);
+    }
+
+    #[divan::bench(args = supported_content_configs("erasure"))]
+    fn decode_f_missing(bencher: Bencher, args: &(BenchConfig, Content)) {
+        bench_decode_f_missing(bencher, "erasure", args);
+    }
+}
+
+// ============================================================================
+// reed-solomon-erasure GF(2^16) benchmarks
+// ============================================================================
+
+#[cfg(feature = "erasure")]
+#[divan::bench_group(name = "content_erasure16")]
+mod content_erasure16 {
+    use super::*;
+
+    #[divan::bench(args = supported_content_configs("erasure16"))]
+    fn encode(bencher: Bencher, args: &(BenchConfig, Content)) {
+        bench_encode(bencher, "erasure16", args);
+    }
+
+    #[divan::bench(args = supported_content_configs("erasure16"))]
+    fn decode_f_missing(bencher: Bencher, args: &(BenchConfig, Content)) {
+        bench_decode_f_missing(bencher, "erasure16", args);
+    }
+}
+
+// ============================================================================
+// reed-solomon-novelpoly benchmarks
+// ============================================================================
+
+#[cfg(feature = "novelpoly")]
+#[divan::bench_group(name = "content_novelpoly")]
+mod content_novelpoly {
+    use super::*;
+
+    #[divan::bench(args = supported_content_configs("novelpoly"))]
+    fn encode(bencher: Bencher, args: &(BenchConfig, Content)) {
+        bench_encode(bencher, "novelpoly", args);
+    }
+
+    #[divan::bench(args = supported_content_configs("novelpoly"))]
+    fn decode_f_missing(bencher: Bencher, args: &(BenchConfig, Content)) {
+        bench_decode_f_missing(bencher, "novelpoly", args);
+    }
+}
+
+// ============================================================================
+// reed-solomon-16 benchmarks
+// ============================================================================
+
+#[cfg(feature = "rs16")]
+#[divan::bench_group(name = "content_rs16")]
+mod content_rs16 {
+    use super::*;
+
+    #[divan::bench(args = supported_content_configs("rs16"))]
+    fn encode(bencher: Bencher, args: &(BenchConfig, Content)) {
+        bench_encode(bencher, "rs16", args);
+    }
+
+    #[divan::bench(args = supported_content_configs("rs16"))]
+    fn decode_f_missing(bencher: Bencher, args: &(BenchConfig, Content)) {
+        bench_decode_f_missing(bencher, "rs16", args);
+    }
+}
+
+// ============================================================================
+// reed-solomon-simd benchmarks
+// ============================================================================
+
+#[cfg(feature = "simd")]
+#[divan::bench_group(name = "content_simd")]
+mod content_simd {
+    use super::*;
+
+    #[divan::bench(args = supported_content_configs("simd"))]
+    fn encode(bencher: Bencher, args: &(BenchConfig, Content)) {
+        bench_encode(bencher, "simd", args);
+    }
+
+    #[divan::bench(args = supported_content_configs("simd"))]
+    fn decode_f_missing(bencher: Bencher, args: &(BenchConfig, Content)) {
+        bench_decode_f_missing(bencher, "simd", args);
+    }
+}
@@ -1,22 +1,122 @@
 mod common;
 
-use common::{all_configs, generate_data, BenchConfig};
+// Only used by the backend groups, each behind its feature
+#[cfg(any(
+    feature = "erasure",
+    feature = "novelpoly",
+    feature = "rs16",
+    feature = "simd"
+))]
+use common::{generate_data, supported_configs, supported_unaligned_configs, BenchConfig};
+#[cfg(any(
+    feature = "erasure",
+    feature = "novelpoly",
+    feature = "rs16",
+    feature = "simd"
+))]
 use divan::{black_box, Bencher};
+#[cfg(any(
+    feature = "erasure",
+    feature = "novelpoly",
+    feature = "rs16",
+    feature = "simd"
+))]
+use reed_solomon_benchmark::codec;
 
 fn main() {
     divan::main();
 }
 
+/// Times decoding an unaligned payload with the first F shards lost.
+///
+/// The groups below hand the crates 64-byte-aligned shards directly; this goes
+/// through the library adapters, which pad the payload on encode and cut the
+/// restored data back to its exact length on decode.
+#[cfg(any(
+    feature = "erasure",
+    feature = "novelpoly",
+    feature = "rs16",
+    feature = "simd"
+))]
+fn bench_unaligned(bencher: Bencher, backend: &str, config: BenchConfig) {
+    let codec = codec::backend(backend).expect("bench group for a compiled-in backend");
+    let data = generate_data(config.data_size);
+    let (k, m) = (config.data_shards(), config.coding_shards());
+    let received: Vec<Option<Vec<u8>>> = codec
+        .encode(k, m, &data)
+        .unwrap()
+        .into_iter()
+        .enumerate()
+        .map(|(i, shard)| (i >= config.f).then_some(shard))
+        .collect();
+
+    bencher.bench_local(|| {
+        let decoded = codec.decode(k, m, &received, data.len()).unwrap();
+        black_box(decoded);
+    });
+}
+
+// ============================================================================
+// Unaligned payloads, one bench per backend
+// ============================================================================
+
+#[cfg(any(
+    feature = "erasure",
+    feature = "novelpoly",
+    feature = "rs16",
+    feature = "simd"
+))]
+#[divan::bench_group(name = "decode_unaligned")]
+mod decode_unaligned {
+    use super::*;
+
+    #[cfg(feature = "erasure")]
+    #[divan::bench(args = supported_unaligned_configs("erasure"))]
+    fn erasure(bencher: Bencher, config: BenchConfig) {
+        bench_unaligned(bencher, "erasure", config);
+    }
+
+    #[cfg(feature = "erasure")]
+    #[divan::bench(args = supported_unaligned_configs("erasure16"))]
+    fn erasure16(bencher: Bencher, config: BenchConfig) {
+        bench_unaligned(bencher, "erasure16", config);
+    }
+
+    #[cfg(feature = "novelpoly")]
+    #[divan::bench(args = supported_unaligned_configs("novelpoly"))]
+    fn novelpoly(bencher: Bencher, config: BenchConfig) {
+        bench_unaligned(bencher, "novelpoly", config);
+    }
+
+    #[cfg(feature = "rs16")]
+    #[divan::bench(args = supported_unaligned_configs("rs16"))]
+    fn rs16(bencher: Bencher, config: BenchConfig) {
+        bench_unaligned(bencher, "rs16", config);
+    }
+
+    #[cfg(feature = "simd")]
+    #[divan::bench(args = supported_unaligned_configs("simd"))]
+    fn simd(bencher: Bencher, config: BenchConfig) {
+        bench_unaligned(bencher, "simd", config);
+    }
+}
+
 // ============================================================================
 // reed-solomon-erasure benchmarks
 // ============================================================================
 
+#[cfg(feature = "erasure")]
 #[divan::bench_group(name = "decode_erasure")]
 mod decode_erasure {
     use super::*;
     use reed_solomon_erasure::galois_8::ReedSolomon;
 
-    fn bench_config(bencher: Bencher, config: BenchConfig, missing_count: usize) {
+    fn bench_config(
+        bencher: Bencher,
+        config: BenchConfig,
+        missing_count: usize,
+        with_parity: bool,
+    ) {
         let data = generate_data(config.data_size);
         let shard_size = config.shard_size();
 
@@ -57,33 +157,150 @@ mod decode_erasure {
                 })
                 .collect();
 
-            encoder.reconstruct(&mut shards_clone).unwrap();
+            // Data-only recovery matches what the other backends' decoders return
+            if with_parity {
+                encoder.reconstruct(&mut shards_clone).unwrap();
+            } else {
+                encoder.reconstruct_data(&mut shards_clone).unwrap();
+            }
             black_box(shards_clone);
         });
     }
 
-    #[divan::bench(args = all_configs())]
+    #[divan::bench(args = supported_configs("erasure"))]
     fn decode_1_missing(bencher: Bencher, config: BenchConfig) {
-        bench_config(bencher, config, 1);
+        bench_config(bencher, config, 1, false);
     }
 
-    #[divan::bench(args = all_configs())]
+    #[divan::bench(args = supported_configs("erasure"))]
     fn decode_f_missing(bencher: Bencher, config: BenchConfig) {
-        bench_config(bencher, config, config.f);
+        bench_config(bencher, config, config.f, false);
     }
 
-    #[divan::bench(args = all_configs())]
+    #[divan::bench(args = supported_configs("erasure"))]
     fn decode_2f_missing(bencher: Bencher, config: BenchConfig) {
-        bench_config(bencher, config, 2 * config.f);
+        bench_config(bencher, config, 2 * config.f, false);
+    }
+
+    #[divan::bench(args = supported_configs("erasure"))]
+    fn reconstruct_1_missing(bencher: Bencher, config: BenchConfig) {
+        bench_config(bencher, config, 1, true);
+    }
+
+    #[divan::bench(args = supported_configs("erasure"))]
+    fn reconstruct_f_missing(bencher: Bencher, config: BenchConfig) {
+        bench_config(bencher, config, config.f, true);
+    }
+
+    #[divan::bench(args = supported_configs("erasure"))]
+    fn reconstruct_2f_missing(bencher: Bencher, config: BenchConfig) {
+        bench_config(bencher, config, 2 * config.f, true);
     }
 }
 
+// ============================================================================
+// reed-solomon-erasure GF(2^16) benchmarks
+// ============================================================================
+
+#[cfg(feature = "erasure")]
+#[divan::bench_group(name = "decode_erasure16")]
+mod decode_erasure16 {
+    use super::*;
+    use reed_solomon_erasure::galois_16::ReedSolomon;
+
+    fn bench_config(
+        bencher: Bencher,
+        config: BenchConfig,
+        missing_count: usize,
+        with_parity: bool,
+    ) {
+        let data = generate_data(config.data_size);
+        let shard_size = config.shard_size();
+
+        // Prepare and encode shards as GF(2^16) symbols (shard_size is always even)
+        let encoder = ReedSolomon::new(config.data_shards(), config.coding_shards()).unwrap();
+
+        let mut shards: Vec<Vec<[u8; 2]>> = Vec::new();
+        for i in 0..config.data_shards() {
+            let start = i * shard_size;
+            let end = std::cmp::min(start + shard_size, data.len());
+            let mut shard = data[start..end].to_vec();
+            shard.resize(shard_size, 0);
+            shards.push(shard.chunks_exact(2).map(|s| [s[0], s[1]]).collect());
+        }
+
+        for _ in 0..config.coding_shards() {
+            shards.push(vec![[0u8; 2]; shard_size / 2]);
+        }
+
+        encoder.encode(&mut shards).unwrap();
+
+        bencher.bench_local(|| {
+            let mut shards_clone: Vec<Option<Vec<[u8; 2]>>> = shards
+                .iter()
+                .enumerate()
+                .map(|(i, shard)| {
+                    if i < missing_count {
+                        None
+                    } else {
+                        Some(shard.clone())
+                    }
+                })
+                .collect();
+
+            // Data-only recovery matches what the other backends' decoders return
+            if with_parity {
+                encoder.reconstruct(&mut shards_clone).unwrap();
+            } else {
+                encoder.reconstruct_data(&mut shards_clone).unwrap();
+            }
+            black_box(shards_clone);
+        });
+    }
+
+    #[divan::bench(args = supported_configs("erasure16"))]
+    fn decode_1_missing(bencher: Bencher, config: BenchConfig) {
+        bench_config(bencher, config, 1, false);
+    }
+
+    #[divan::bench(args = supported_configs("erasure16"))]
+    fn decode_f_missing(bencher: Bencher, config: BenchConfig) {
+        bench_config(bencher, config, config.f, false);
+    }
+
+    #[divan::bench(args = supported_configs("erasure16"))]
+    fn decode_2f_missing(bencher: Bencher, config: BenchConfig) {
+        bench_config(bencher, config, 2 * config.f, false);
+    }
+
+    #[divan::bench(args = supported_configs("erasure16"))]
+    fn reconstruct_1_missing(bencher: Bencher, config: BenchConfig) {
+        bench_config(bencher, config, 1, true);
+    }
+
+    #[divan::bench(args = supported_configs("erasure16"))]
+    fn reconstruct_f_missing(bencher: Bencher, config: BenchConfig) {
+        bench_config(bencher, config, config.f, true);
+    }
+
+    #[divan::bench(args = supported_configs("erasure16"))]
+    fn reconstruct_2f_missing(bencher: Bencher, config: BenchConfig) {
+        bench_config(bencher, config, 2 * config.f, true);
+    }
+}
+
+#[cfg(feature = "novelpoly")]
 #[divan::bench_group(name = "decode_novelpoly")]
 mod decode_novelpoly {
     use super::*;
     use reed_solomon_novelpoly::{CodeParams, WrappedShard};
 
-    fn bench_config(bencher: Bencher, config: BenchConfig, missing_count: usize) {
+    fn bench_config(
+        bencher: Bencher,
+        config: BenchConfig,
+        missing_count: usize,
+        with_parity: bool,
+    ) {
         let data = generate_data(config.data_size);
         let total_shards = config.total_shards();
         let data_shards = config.data_shards();
@@ -112,23 +329,45 @@ mod decode_novelpoly {
             let recovered =
                 reed_solomon_novelpoly::reconstruct(shards_with_missing.clone(), total_shards)
                     .unwrap();
-            black_box(recovered);
+
+            // novelpoly only recovers the payload; parity comes from re-encoding it
+            if with_parity {
+                let shards: Vec<WrappedShard> = encoder.encode(&recovered).unwrap();
+                black_box(shards);
+            } else {
+                black_box(recovered);
+            }
         });
     }
 
-    #[divan::bench(args = all_configs())]
+    #[divan::bench(args = supported_configs("novelpoly"))]
     fn decode_1_missing(bencher: Bencher, config: BenchConfig) {
-        bench_config(bencher, config, 1);
+        bench_config(bencher, config, 1, false);
     }
 
-    #[divan::bench(args = all_configs())]
+    #[divan::bench(args = supported_configs("novelpoly"))]
     fn decode_f_missing(bencher: Bencher, config: BenchConfig) {
-        bench_config(bencher, config, config.f);
+        bench_config(bencher, config, config.f, false);
     }
 
-    #[divan::bench(args = all_configs())]
+    #[divan::bench(args = supported_configs("novelpoly"))]
     fn decode_2f_missing(bencher: Bencher, config: BenchConfig) {
-        bench_config(bencher, config, 2 * config.f);
+        bench_config(bencher, config, 2 * config.f, false);
+    }
+
+    #[divan::bench(args = supported_configs("novelpoly"))]
+    fn reconstruct_1_missing(bencher: Bencher, config: BenchConfig) {
+        bench_config(bencher, config, 1, true);
+    }
+
+    #[divan::bench(args = supported_configs("novelpoly"))]
+    fn reconstruct_f_missing(bencher: Bencher, config: BenchConfig) {
+        bench_config(bencher, config, config.f, true);
+    }
+
+    #[divan::bench(args = supported_configs("novelpoly"))]
+    fn reconstruct_2f_missing(bencher: Bencher, config: BenchConfig) {
+        bench_config(bencher, config, 2 * config.f, true);
     }
 }
 
@@ -136,12 +375,18 @@ mod decode_novelpoly {
 // reed-solomon-16 benchmarks
 // ============================================================================
 
+#[cfg(feature = "rs16")]
 #[divan::bench_group(name = "decode_rs16")]
 mod decode_rs16 {
     use super::*;
     use reed_solomon_16::ReedSolomonDecoder;
 
-    fn bench_config(bencher: Bencher, config: BenchConfig, missing_count: usize) {
+    fn bench_config(
+        bencher: Bencher,
+        config: BenchConfig,
+        missing_count: usize,
+        with_parity: bool,
+    ) {
         let data = generate_data(config.data_size);
         let shard_bytes = config.shard_size(); // Use aligned size for rs16
 
@@ -183,23 +428,59 @@ mod decode_rs16 {
             }
 
             let result = decoder.decode().unwrap();
-            black_box(result);
+
+            // The decoder only restores originals; parity comes from re-encoding them
+            if with_parity {
+                // Missing originals must come back from the decoder, the rest were handed to it
+                let restored: Vec<&[u8]> = (0..config.data_shards())
+                    .map(|index| {
+                        if index < missing_count {
+                            result
+                                .restored_original(index)
+                                .expect("decoder did not restore a missing original")
+                        } else {
+                            &original_shards[index]
+                        }
+                    })
+                    .collect();
+                let recovery =
+                    reed_solomon_16::encode(config.data_shards(), config.coding_shards(), restored)
+                        .unwrap();
+                black_box(recovery);
+            } else {
+                black_box(result);
+            }
         });
     }
 
-    #[divan::bench(args = all_configs())]
+    #[divan::bench(args = supported_configs("rs16"))]
     fn decode_1_missing(bencher: Bencher, config: BenchConfig) {
-        bench_config(bencher, config, 1);
+        bench_config(bencher, config, 1, false);
     }
 
-    #[divan::bench(args = all_configs())]
+    #[divan::bench(args = supported_configs("rs16"))]
     fn decode_f_missing(bencher: Bencher, config: BenchConfig) {
-        bench_config(bencher, config, config.f);
+        bench_config(bencher, config, config.f, false);
     }
 
-    #[divan::bench(args = all_configs())]
+    #[divan::bench(args = supported_configs("rs16"))]
     fn decode_2f_missing(bencher: Bencher, config: BenchConfig) {
-        bench_config(bencher, config, 2 * config.f);
+        bench_config(bencher, config, 2 * config.f, false);
+    }
+
+    #[divan::bench(args = supported_configs("rs16"))]
+    fn reconstruct_1_missing(bencher: Bencher, config: BenchConfig) {
+        bench_config(bencher, config, 1, true);
+    }
+
+    #[divan::bench(args = supported_configs("rs16"))]
+    fn reconstruct_f_missing(bencher: Bencher, config: BenchConfig) {
+        bench_config(bencher, config, config.f, true);
+    }
+
+    #[divan::bench(args = supported_configs("rs16"))]
+    fn reconstruct_2f_missing(bencher: Bencher, config: BenchConfig) {
+        bench_config(bencher, config, 2 * config.f, true);
     }
 }
 
@@ -207,12 +488,18 @@ mod decode_rs16 {
 // reed-solomon-simd benchmarks
 // ============================================================================
 
+#[cfg(feature = "simd")]
 #[divan::bench_group(name = "decode_simd")]
 mod decode_simd {
     use super::*;
     use reed_solomon_simd::ReedSolomonDecoder;
 
-    fn bench_config(bencher: Bencher, config: BenchConfig, missing_count: usize) {
+    fn bench_config(
+        bencher: Bencher,
+        config: BenchConfig,
+        missing_count: usize,
+        with_parity: bool,
+    ) {
         let data = generate_data(config.data_size);
         let shard_bytes = config.shard_size(); // Use aligned size for simd
 
@@ -254,22 +541,61 @@ mod decode_simd {
             }
 
             let result = decoder.decode().unwrap();
-            black_box(result);
+
+            // The decoder only restores originals; parity comes from re-encoding them
+            if with_parity {
+                // Missing originals must come back from the decoder, the rest were handed to it
+                let restored: Vec<&[u8]> = (0..config.data_shards())
+                    .map(|index| {
+                        if index < missing_count {
+                            result
+                                .restored_original(index)
+                                .expect("decoder did not restore a missing original")
+                        } else {
+                            &original_shards[index]
+                        }
+                    })
+                    .collect();
+                let recovery = reed_solomon_simd::encode(
+                    config.data_shards(),
+                    config.coding_shards(),
+                    restored,
+                )
+                .unwrap();
+                black_box(recovery);
+            } else {
+                black_box(result);
+            }
         });
     }
 
-    #[divan::bench(args = all_configs())]
+    #[divan::bench(args = supported_configs("simd"))]
     fn decode_1_missing(bencher: Bencher, config: BenchConfig) {
-        bench_config(bencher, config, 1);
+        bench_config(bencher, config, 1, false);
     }
 
-    #[divan::bench(args = all_configs())]
+    #[divan::bench(args = supported_configs("simd"))]
     fn decode_f_missing(bencher: Bencher, config: BenchConfig) {
-        bench_config(bencher, config, config.f);
+        bench_config(bencher, config, config.f, false);
     }
 
-    #[divan::bench(args = all_configs())]
+    #[divan::bench(args = supported_configs("simd"))]
     fn decode_2f_missing(bencher: Bencher, config: BenchConfig) {
-        bench_config(bencher, config, 2 * config.f);
+        bench_config(bencher, config, 2 * config.f, false);
+    }
+
+    #[divan::bench(args = supported_configs("simd"))]
+    fn reconstruct_1_missing(bencher: Bencher, config: BenchConfig) {
+        bench_config(bencher, config, 1, true);
+    }
+
+    #[divan::bench(args = supported_configs("simd"))]
+    fn reconstruct_f_missing(bencher: Bencher, config: BenchConfig) {
+        bench_config(bencher, config, config.f, true);
+    }
+
+    #[divan::bench(args = supported_configs("simd"))]
+    fn reconstruct_2f_missing(bencher: Bencher, config: BenchConfig) {
+        bench_config(bencher, config, 2 * config.f, true);
     }
 }
@@ -1,6 +1,19 @@
 mod common;
 
-use common::{all_configs, generate_data, BenchConfig};
+// Only used by the backend groups, each behind its feature
+#[cfg(any(
+    feature = "erasure",
+    feature = "novelpoly",
+    feature = "rs16",
+    feature = "simd"
+))]
+use common::{generate_data, supported_configs, BenchConfig};
+#[cfg(any(
+    feature = "erasure",
+    feature = "novelpoly",
+    feature = "rs16",
+    feature = "simd"
+))]
 use divan::{black_box, Bencher};
 
 fn main() {
@@ -11,6 +24,7 @@ fn main() {
 // reed-solomon-erasure benchmarks
 // ============================================================================
 
+#[cfg(feature = "erasure")]
 #[divan::bench_group(name = "encode_erasure")]
 mod encode_erasure {
     use super::*;
@@ -44,7 +58,51 @@ mod encode_erasure {
         });
     }
 
-    #[divan::bench(args = all_configs())]
+    #[divan::bench(args = supported_configs("erasure"))]
+    fn encode(bencher: Bencher, config: BenchConfig) {
+        bench_config(bencher, config);
+    }
+}
+
+// ============================================================================
+// reed-solomon-erasure GF(2^16) benchmarks
+// ============================================================================
+
+#[cfg(feature = "erasure")]
+#[divan::bench_group(name = "encode_erasure16")]
+mod encode_erasure16 {
+    use super::*;
+    use reed_solomon_erasure::galois_16::ReedSolomon;
+
+    fn bench_config(bencher: Bencher, config: BenchConfig) {
+        let data = generate_data(config.data_size);
+        let shard_size = config.shard_size();
+
+        // Prepare data shards as GF(2^16) symbols (shard_size is always even)
+        let mut shards: Vec<Vec<[u8; 2]>> = Vec::new();
+        for i in 0..config.data_shards() {
+            let start = i * shard_size;
+            let end = std::cmp::min(start + shard_size, data.len());
+            let mut shard = data[start..end].to_vec();
+            shard.resize(shard_size, 0);
+            shards.push(shard.chunks_exact(2).map(|s| [s[0], s[1]]).collect());
+        }
+
+        // Add empty parity shards
+        for _ in 0..config.coding_shards() {
+            shards.push(vec![[0u8; 2]; shard_size / 2]);
+        }
+
+        bencher.bench_local(|| {
+            let encoder = ReedSolomon::new(config.data_shards(), config.coding_shards()).unwrap();
+
+            let mut shards_clone = shards.clone();
+            encoder.encode(&mut shards_clone).unwrap();
+            black_box(shards_clone);
+        });
+    }
+
+    #[divan::bench(args = supported_configs("erasure16"))]
     fn encode(bencher: Bencher, config: BenchConfig) {
         bench_config(bencher, config);
     }
@@ -54,6 +112,7 @@ mod encode_erasure {
 // reed-solomon-novelpoly benchmarks
 // ============================================================================
 
+#[cfg(feature = "novelpoly")]
 #[divan::bench_group(name = "encode_novelpoly")]
 mod encode_novelpoly {
     use super::*;
@@ -73,7 +132,7 @@ mod encode_novelpoly {
         });
     }
 
-    #[divan::bench(args = all_configs())]
+    #[divan::bench(args = supported_configs("novelpoly"))]
     fn encode(bencher: Bencher, config: BenchConfig) {
         bench_config(bencher, config);
     }
@@ -83,6 +142,7 @@ mod encode_novelpoly {
 // reed-solomon-16 benchmarks
 // ============================================================================
 
+#[cfg(feature = "rs16")]
 #[divan::bench_group(name = "encode_rs16")]
 mod encode_rs16 {
     use super::*;
@@ -111,7 +171,7 @@ mod encode_rs16 {
         });
     }
 
-    #[divan::bench(args = all_configs())]
+    #[divan::bench(args = supported_configs("rs16"))]
     fn encode(bencher: Bencher, config: BenchConfig) {
         bench_config(bencher, config);
     }
@@ -121,6 +181,7 @@ mod encode_rs16 {
 // reed-solomon-simd benchmarks
 // ============================================================================
 
+#[cfg(feature = "simd")]
 #[divan::bench_group(name = "encode_simd")]
 mod encode_simd {
     use super::*;
@@ -149,7 +210,7 @@ mod encode_simd {
         });
     }
 
-    #[divan::bench(args = all_configs())]
+    #[divan::bench(args = supported_configs("simd"))]
     fn encode(bencher: Bencher, config: BenchConfig) {
         bench_config(bencher, config);
     }
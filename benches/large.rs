@@ -0,0 +1,288 @@
+mod common;
+
+// Only used by the backend groups, each behind its feature
+#[cfg(any(
+    feature = "erasure",
+    feature = "novelpoly",
+    feature = "rs16",
+    feature = "simd"
+))]
+use common::{generate_data, supported_large_configs, BenchConfig};
+#[cfg(any(
+    feature = "erasure",
+    feature = "novelpoly",
+    feature = "rs16",
+    feature = "simd"
+))]
+use divan::{black_box, Bencher};
+
+fn main() {
+    divan::main();
+}
+
+// Large committees (hundreds to thousands of shards) only fit the GF(2^16)
+// backends: erasure16, novelpoly, rs16 and simd. Each iteration takes long
+// enough that a few samples suffice.
+// galois_16 needs seconds per encode at F=500, so it gets even fewer.
+
+// ============================================================================
+// reed-solomon-erasure GF(2^16) benchmarks
+// ============================================================================
+
+#[cfg(feature = "erasure")]
+#[divan::bench_group(name = "large_erasure16", sample_count = 3, sample_size = 1)]
+mod large_erasure16 {
+    use super::*;
+    use reed_solomon_erasure::galois_16::ReedSolomon;
+
+    fn encoded_shards(config: BenchConfig) -> Vec<Vec<[u8; 2]>> {
+        let data = generate_data(config.data_size);
+        let shard_size = config.shard_size();
+
+        // Prepare data shards as GF(2^16) symbols (shard_size is always even)
+        let mut shards: Vec<Vec<[u8; 2]>> = Vec::new();
+        for i in 0..config.data_shards() {
+            let start = std::cmp::min(i * shard_size, data.len());
+            let end = std::cmp::min(start + shard_size, data.len());
+            let mut shard = data[start..end].to_vec();
+            shard.resize(shard_size, 0);
+            shards.push(shard.chunks_exact(2).map(|s| [s[0], s[1]]).collect());
+        }
+
+        // Add empty parity shards
+        for _ in 0..config.coding_shards() {
+            shards.push(vec![[0u8; 2]; shard_size / 2]);
+        }
+        shards
+    }
+
+    #[divan::bench(args = supported_large_configs("erasure16"))]
+    fn encode(bencher: Bencher, config: BenchConfig) {
+        let shards = encoded_shards(config);
+
+        bencher.bench_local(|| {
+            let encoder = ReedSolomon::new(config.data_shards(), config.coding_shards()).unwrap();
+
+            let mut shards_clone = shards.clone();
+            encoder.encode(&mut shards_clone).unwrap();
+            black_box(shards_clone);
+        });
+    }
+
+    #[divan::bench(args = supported_large_configs("erasure16"))]
+    fn decode_f_missing(bencher: Bencher, config: BenchConfig) {
+        let encoder = ReedSolomon::new(config.data_shards(), config.coding_shards()).unwrap();
+        let mut shards = encoded_shards(config);
+        encoder.encode(&mut shards).unwrap();
+
+        bencher.bench_local(|| {
+            let mut shards_clone: Vec<Option<Vec<[u8; 2]>>> = shards
+                .iter()
+                .enumerate()
+                .map(|(i, shard)| (i >= config.f).then(|| shard.clone()))
+                .collect();
+
+            encoder.reconstruct_data(&mut shards_clone).unwrap();
+            black_box(shards_clone);
+        });
+    }
+}
+
+// ============================================================================
+// reed-solomon-novelpoly benchmarks
+// ============================================================================
+
+#[cfg(feature = "novelpoly")]
+#[divan::bench_group(name = "large_novelpoly", sample_count = 10, sample_size = 1)]
+mod large_novelpoly {
+    use super::*;
+    use reed_solomon_novelpoly::{CodeParams, WrappedShard};
+
+    #[divan::bench(args = supported_large_configs("novelpoly"))]
+    fn encode(bencher: Bencher, config: BenchConfig) {
+        let data = generate_data(config.data_size);
+
+        bencher.bench_local(|| {
+            let params =
+                CodeParams::derive_parameters(config.total_shards(), config.data_shards()).unwrap();
+            let shards: Vec<WrappedShard> = params.make_encoder().encode(&data).unwrap();
+            black_box(shards);
+        });
+    }
+
+    #[divan::bench(args = supported_large_configs("novelpoly"))]
+    fn decode_f_missing(bencher: Bencher, config: BenchConfig) {
+        let data = generate_data(config.data_size);
+        let params =
+            CodeParams::derive_parameters(config.total_shards(), config.data_shards()).unwrap();
+        let received: Vec<Option<WrappedShard>> = params
+            .make_encoder()
+            .encode(&data)
+            .unwrap()
+            .into_iter()
+            .enumerate()
+            .map(|(i, shard)| (i >= config.f).then_some(shard))
+            .collect();
+
+        bencher.bench_local(|| {
+            let recovered =
+                reed_solomon_novelpoly::reconstruct(received.clone(), config.total_shards())
+                    .unwrap();
+            black_box(recovered);
+        });
+    }
+}
+
+// ============================================================================
+// reed-solomon-16 benchmarks
+// ============================================================================
+
+#[cfg(feature = "rs16")]
+#[divan::bench_group(name = "large_rs16", sample_count = 10, sample_size = 1)]
+mod large_rs16 {
+    use super::*;
+    use reed_solomon_16::{ReedSolomonDecoder, ReedSolomonEncoder};
+
+    fn original_shards(config: BenchConfig) -> Vec<Vec<u8>> {
+        let data = generate_data(config.data_size);
+        let shard_bytes = config.shard_size(); // Use aligned size for rs16
+
+        let mut original_shards = Vec::new();
+        for i in 0..config.data_shards() {
+            let start = std::cmp::min(i * shard_bytes, data.len());
+            let end = std::cmp::min(start + shard_bytes, data.len());
+            let mut shard = data[start..end].to_vec();
+            shard.resize(shard_bytes, 0);
+            original_shards.push(shard);
+        }
+        original_shards
+    }
+
+    #[divan::bench(args = supported_large_configs("rs16"))]
+    fn encode(bencher: Bencher, config: BenchConfig) {
+        let original_shards = original_shards(config);
+
+        bencher.bench_local(|| {
+            let mut encoder = ReedSolomonEncoder::new(
+                config.data_shards(),
+                config.coding_shards(),
+                config.shard_size(),
+            )
+            .unwrap();
+            for shard in &original_shards {
+                encoder.add_original_shard(shard).unwrap();
+            }
+
+            let result = encoder.encode().unwrap();
+            black_box(result);
+        });
+    }
+
+    #[divan::bench(args = supported_large_configs("rs16"))]
+    fn decode_f_missing(bencher: Bencher, config: BenchConfig) {
+        let original_shards = original_shards(config);
+        let recovery = reed_solomon_16::encode(
+            config.data_shards(),
+            config.coding_shards(),
+            &original_shards,
+        )
+        .unwrap();
+
+        bencher.bench_local(|| {
+            let mut decoder = ReedSolomonDecoder::new(
+                config.data_shards(),
+                config.coding_shards(),
+                config.shard_size(),
+            )
+            .unwrap();
+
+            // Lose the first F originals and replace them with F recovery shards
+            for (index, shard) in original_shards.iter().enumerate().skip(config.f) {
+                decoder.add_original_shard(index, shard).unwrap();
+            }
+            for (index, shard) in recovery.iter().enumerate().take(config.f) {
+                decoder.add_recovery_shard(index, shard).unwrap();
+            }
+
+            let result = decoder.decode().unwrap();
+            black_box(result);
+        });
+    }
+}
+
+// ============================================================================
+// reed-solomon-simd benchmarks
+// ============================================================================
+
+#[cfg(feature = "simd")]
+#[divan::bench_group(name = "large_simd", sample_count = 10, sample_size = 1)]
+mod large_simd {
+    use super::*;
+    use reed_solomon_simd::{ReedSolomonDecoder, ReedSolomonEncoder};
+
+    fn original_shards(config: BenchConfig) -> Vec<Vec<u8>> {
+        let data = generate_data(config.data_size);
+        let shard_bytes = config.shard_size(); // Use aligned size for simd
+
+        let mut original_shards = Vec::new();
+        for i in 0..config.data_shards() {
+            let start = std::cmp::min(i * shard_bytes, data.len());
+            let end = std::cmp::min(start + shard_bytes, data.len());
+            let mut shard = data[start..end].to_vec();
+            shard.resize(shard_bytes, 0);
+            original_shards.push(shard);
+        }
+        original_shards
+    }
+
+    #[divan::bench(args = supported_large_configs("simd"))]
+    fn encode(bencher: Bencher, config: BenchConfig) {
+        let original_shards = original_shards(config);
+
+        bencher.bench_local(|| {
+            let mut encoder = ReedSolomonEncoder::new(
+                config.data_shards(),
+                config.coding_shards(),
+                config.shard_size(),
+            )
+            .unwrap();
+            for shard in &original_shards {
+                encoder.add_original_shard(shard).unwrap();
+            }
+
+            let result = encoder.encode().unwrap();
+            black_box(result);
+        });
+    }
+
+    #[divan::bench(args = supported_large_configs("simd"))]
+    fn decode_f_missing(bencher: Bencher, config: BenchConfig) {
+        let original_shards = original_shards(config);
+        let recovery = reed_solomon_simd::encode(
+            config.data_shards(),
+            config.coding_shards(),
+            &original_shards,
+        )
+        .unwrap();
+
+        bencher.bench_local(|| {
+            let mut decoder = ReedSolomonDecoder::new(
+                config.data_shards(),
+                config.coding_shards(),
+                config.shard_size(),
+            )
+            .unwrap();
+
+            // Lose the first F originals and replace them with F recovery shards
+            for (index, shard) in original_shards.iter().enumerate().skip(config.f) {
+                decoder.add_original_shard(index, shard).unwrap();
+            }
+            for (index, shard) in recovery.iter().enumerate().take(config.f) {
+                decoder.add_recovery_shard(index, shard).unwrap();
+            }
+
+            let result = decoder.decode().unwrap();
+            black_box(result);
+        });
+    }
+}
@@ -0,0 +1,182 @@
+mod common;
+
+// Only used by the backend groups, each behind its feature
+#[cfg(any(
+    feature = "erasure",
+    feature = "novelpoly",
+    feature = "rs16",
+    feature = "simd"
+))]
+use common::{generate_data, supported_configs, BenchConfig};
+#[cfg(any(
+    feature = "erasure",
+    feature = "novelpoly",
+    feature = "rs16",
+    feature = "simd"
+))]
+use divan::{black_box, Bencher};
+#[cfg(any(
+    feature = "erasure",
+    feature = "novelpoly",
+    feature = "rs16",
+    feature = "simd"
+))]
+use reed_solomon_benchmark::codec;
+
+fn main() {
+    divan::main();
+}
+
+/// Times regenerating the last `lost` shards, as when the nodes holding them die.
+///
+/// Runs through the library adapters, since `repair` is defined there rather
+/// than by the crates: each backend rebuilds only what the lost shards need.
+#[cfg(any(
+    feature = "erasure",
+    feature = "novelpoly",
+    feature = "rs16",
+    feature = "simd"
+))]
+fn bench_repair(bencher: Bencher, backend: &str, config: BenchConfig, lost: usize) {
+    let codec = codec::backend(backend).expect("bench group for a compiled-in backend");
+    let data = generate_data(config.data_size);
+    let (k, m) = (config.data_shards(), config.coding_shards());
+
+    let shards = codec.encode(k, m, &data).unwrap();
+    let missing: Vec<usize> = (k + m - lost..k + m).collect();
+    let received: Vec<Option<Vec<u8>>> = shards
+        .into_iter()
+        .enumerate()
+        .map(|(i, shard)| (i < k + m - lost).then_some(shard))
+        .collect();
+
+    bencher.bench_local(|| {
+        let repaired = codec.repair(k, m, &received, &missing).unwrap();
+        black_box(repaired);
+    });
+}
+
+// ============================================================================
+// reed-solomon-erasure benchmarks
+// ============================================================================
+
+#[cfg(feature = "erasure")]
+#[divan::bench_group(name = "repair_erasure")]
+mod repair_erasure {
+    use super::*;
+
+    #[divan::bench(args = supported_configs("erasure"))]
+    fn repair_1_missing(bencher: Bencher, config: BenchConfig) {
+        bench_repair(bencher, "erasure", config, 1);
+    }
+
+    #[divan::bench(args = supported_configs("erasure"))]
+    fn repair_f_missing(bencher: Bencher, config: BenchConfig) {
+        bench_repair(bencher, "erasure", config, config.f);
+    }
+
+    #[divan::bench(args = supported_configs("erasure"))]
+    fn repair_2f_missing(bencher: Bencher, config: BenchConfig) {
+        bench_repair(bencher, "erasure", config, 2 * config.f);
+    }
+}
+
+// ============================================================================
+// reed-solomon-erasure GF(2^16) benchmarks
+// ============================================================================
+
+#[cfg(feature = "erasure")]
+#[divan::bench_group(name = "repair_erasure16")]
+mod repair_erasure16 {
+    use super::*;
+
+    #[divan::bench(args = supported_configs("erasure16"))]
+    fn repair_1_missing(bencher: Bencher, config: BenchConfig) {
+        bench_repair(bencher, "erasure16", config, 1);
+    }
+
+    #[divan::bench(args = supported_configs("erasure16"))]
+    fn repair_f_missing(bencher: Bencher, config: BenchConfig) {
+        bench_repair(bencher, "erasure16", config, config.f);
+    }
+
+    #[divan::bench(args = supported_configs("erasure16"))]
+    fn repair_2f_missing(bencher: Bencher, config: BenchConfig) {
+        bench_repair(bencher, "erasure16", config, 2 * config.f);
+    }
+}
+
+// ============================================================================
+// reed-solomon-novelpoly benchmarks
+// ============================================================================
+
+#[cfg(feature = "novelpoly")]
+#[divan::bench_group(name = "repair_novelpoly")]
+mod repair_novelpoly {
+    use super::*;
+
+    #[divan::bench(args = supported_configs("novelpoly"))]
+    fn repair_1_missing(bencher: Bencher, config: BenchConfig) {
+        bench_repair(bencher, "novelpoly", config, 1);
+    }
+
+    #[divan::bench(args = supported_configs("novelpoly"))]
+    fn repair_f_missing(bencher: Bencher, config: BenchConfig) {
+        bench_repair(bencher, "novelpoly", config, config.f);
+    }
+
+    #[divan::bench(args = supported_configs("novelpoly"))]
+    fn repair_2f_missing(bencher: Bencher, config: BenchConfig) {
+        bench_repair(bencher, "novelpoly", config, 2 * config.f);
+    }
+}
+
+// ============================================================================
+// reed-solomon-16 benchmarks
+// ============================================================================
+
+#[cfg(feature = "rs16")]
+#[divan::bench_group(name = "repair_rs16")]
+mod repair_rs16 {
+    use super::*;
+
+    #[divan::bench(args = supported_configs("rs16"))]
+    fn repair_1_missing(bencher: Bencher, config: BenchConfig) {
+        bench_repair(bencher, "rs16", config, 1);
+    }
+
+    #[divan::bench(args = supported_configs("rs16"))]
+    fn repair_f_missing(bencher: Bencher, config: BenchConfig) {
+        bench_repair(bencher, "rs16", config, config.f);
+    }
+
+    #[divan::bench(args = supported_configs("rs16"))]
+    fn repair_2f_missing(bencher: Bencher, config: BenchConfig) {
+        bench_repair(bencher, "rs16", config, 2 * config.f);
+    }
+}
+
+// ============================================================================
+// reed-solomon-simd benchmarks
+// ============================================================================
+
+#[cfg(feature = "simd")]
+#[divan::bench_group(name = "repair_simd")]
+mod repair_simd {
+    use super::*;
+
+    #[divan::bench(args = supported_configs("simd"))]
+    fn repair_1_missing(bencher: Bencher, config: BenchConfig) {
+        bench_repair(bencher, "simd", config, 1);
+    }
+
+    #[divan::bench(args = supported_configs("simd"))]
+    fn repair_f_missing(bencher: Bencher, config: BenchConfig) {
+        bench_repair(bencher, "simd", config, config.f);
+    }
+
+    #[divan::bench(args = supported_configs("simd"))]
+    fn repair_2f_missing(bencher: Bencher, config: BenchConfig) {
+        bench_repair(bencher, "simd", config, 2 * config.f);
+    }
+}
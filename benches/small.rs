@@ -0,0 +1,410 @@
+mod common;
+
+// Only used by the backend groups, each behind its feature
+#[cfg(any(
+    feature = "erasure",
+    feature = "novelpoly",
+    feature = "rs16",
+    feature = "simd"
+))]
+use common::{generate_data, supported_small_configs, BenchConfig};
+#[cfg(any(
+    feature = "erasure",
+    feature = "novelpoly",
+    feature = "rs16",
+    feature = "simd"
+))]
+use divan::{black_box, Bencher};
+use reed_solomon_benchmark::{codec, config, report};
+
+fn main() {
+    // Padding does not depend on timing, so it is printed once up front
+    print!(
+        "{}",
+        report::render_padding(&codec::backends(), &config::small_configs())
+    );
+    println!();
+    divan::main();
+}
+
+// Consensus votes and certificates are 64B-64KB, where constructing the
+// encoder can cost as much as coding. `setup` times the construction alone;
+// `encode` and `decode_f_missing` build their encoder or decoder inside the
+// timed loop, so they are the full cost of one message.
+
+// ============================================================================
+// reed-solomon-erasure benchmarks
+// ============================================================================
+
+#[cfg(feature = "erasure")]
+#[divan::bench_group(name = "small_erasure")]
+mod small_erasure {
+    use super::*;
+    use reed_solomon_erasure::galois_8::ReedSolomon;
+
+    fn encoded_shards(config: BenchConfig) -> Vec<Vec<u8>> {
+        let data = generate_data(config.data_size);
+        let shard_size = config.shard_size();
+
+        let mut shards: Vec<Vec<u8>> = Vec::new();
+        for i in 0..config.data_shards() {
+            let start = std::cmp::min(i * shard_size, data.len());
+            let end = std::cmp::min(start + shard_size, data.len());
+            let mut shard = data[start..end].to_vec();
+            shard.resize(shard_size, 0);
+            shards.push(shard);
+        }
+        for _ in 0..config.coding_shards() {
+            shards.push(vec![0u8; shard_size]);
+        }
+        shards
+    }
+
+    #[divan::bench(args = supported_small_configs("erasure"))]
+    fn setup(bencher: Bencher, config: BenchConfig) {
+        bencher.bench_local(|| {
+            let encoder = ReedSolomon::new(config.data_shards(), config.coding_shards()).unwrap();
+            black_box(encoder);
+        });
+    }
+
+    #[divan::bench(args = supported_small_configs("erasure"))]
+    fn encode(bencher: Bencher, config: BenchConfig) {
+        let shards = encoded_shards(config);
+
+        bencher.bench_local(|| {
+            let encoder = ReedSolomon::new(config.data_shards(), config.coding_shards()).unwrap();
+
+            let mut shards_clone = shards.clone();
+            encoder.encode(&mut shards_clone).unwrap();
+            black_box(shards_clone);
+        });
+    }
+
+    #[divan::bench(args = supported_small_configs("erasure"))]
+    fn decode_f_missing(bencher: Bencher, config: BenchConfig) {
+        let mut shards = encoded_shards(config);
+        ReedSolomon::new(config.data_shards(), config.coding_shards())
+            .unwrap()
+            .encode(&mut shards)
+            .unwrap();
+
+        bencher.bench_local(|| {
+            let encoder = ReedSolomon::new(config.data_shards(), config.coding_shards()).unwrap();
+
+            let mut shards_clone: Vec<Option<Vec<u8>>> = shards
+                .iter()
+                .enumerate()
+                .map(|(i, shard)| (i >= config.f).then(|| shard.clone()))
+                .collect();
+            encoder.reconstruct_data(&mut shards_clone).unwrap();
+            black_box(shards_clone);
+        });
+    }
+}
+
+// ============================================================================
+// reed-solomon-erasure GF(2^16) benchmarks
+// ============================================================================
+
+#[cfg(feature = "erasure")]
+#[divan::bench_group(name = "small_erasure16")]
+mod small_erasure16 {
+    use super::*;
+    use reed_solomon_erasure::galois_16::ReedSolomon;
+
+    fn encoded_shards(config: BenchConfig) -> Vec<Vec<[u8; 2]>> {
+        let data = generate_data(config.data_size);
+        let shard_size = config.shard_size();
+
+        // Prepare data shards as GF(2^16) symbols (shard_size is always even)
+        let mut shards: Vec<Vec<[u8; 2]>> = Vec::new();
+        for i in 0..config.data_shards() {
+            let start = std::cmp::min(i * shard_size, data.len());
+            let end = std::cmp::min(start + shard_size, data.len());
+            let mut shard = data[start..end].to_vec();
+            shard.resize(shard_size, 0);
+            shards.push(shard.chunks_exact(2).map(|s| [s[0], s[1]]).collect());
+        }
+        for _ in 0..config.coding_shards() {
+            shards.push(vec![[0u8; 2]; shard_size / 2]);
+        }
+        shards
+    }
+
+    #[divan::bench(args = supported_small_configs("erasure16"))]
+    fn setup(bencher: Bencher, config: BenchConfig) {
+        bencher.bench_local(|| {
+            let encoder = ReedSolomon::new(config.data_shards(), config.coding_shards()).unwrap();
+            black_box(encoder);
+        });
+    }
+
+    #[divan::bench(args = supported_small_configs("erasure16"))]
+    fn encode(bencher: Bencher, config: BenchConfig) {
+        let shards = encoded_shards(config);
+
+        bencher.bench_local(|| {
+            let encoder = ReedSolomon::new(config.data_shards(), config.coding_shards()).unwrap();
+
+            let mut shards_clone = shards.clone();
+            encoder.encode(&mut shards_clone).unwrap();
+            black_box(shards_clone);
+        });
+    }
+
+    #[divan::bench(args = supported_small_configs("erasure16"))]
+    fn decode_f_missing(bencher: Bencher, config: BenchConfig) {
+        let mut shards = encoded_shards(config);
+        ReedSolomon::new(config.data_shards(), config.coding_shards())
+            .unwrap()
+            .encode(&mut shards)
+            .unwrap();
+
+        bencher.bench_local(|| {
+            let encoder = ReedSolomon::new(config.data_shards(), config.coding_shards()).unwrap();
+
+            let mut shards_clone: Vec<Option<Vec<[u8; 2]>>> = shards
+                .iter()
+                .enumerate()
+                .map(|(i, shard)| (i >= config.f).then(|| shard.clone()))
+                .collect();
+            encoder.reconstruct_data(&mut shards_clone).unwrap();
+            black_box(shards_clone);
+        });
+    }
+}
+
+// ============================================================================
+// reed-solomon-novelpoly benchmarks
+// ============================================================================
+
+#[cfg(feature = "novelpoly")]
+#[divan::bench_group(name = "small_novelpoly")]
+mod small_novelpoly {
+    use super::*;
+    use reed_solomon_novelpoly::{CodeParams, WrappedShard};
+
+    #[divan::bench(args = supported_small_configs("novelpoly"))]
+    fn setup(bencher: Bencher, config: BenchConfig) {
+        bencher.bench_local(|| {
+            let params =
+                CodeParams::derive_parameters(config.total_shards(), config.data_shards()).unwrap();
+            black_box(params.make_encoder());
+        });
+    }
+
+    #[divan::bench(args = supported_small_configs("novelpoly"))]
+    fn encode(bencher: Bencher, config: BenchConfig) {
+        let data = generate_data(config.data_size);
+
+        bencher.bench_local(|| {
+            // novelpoly uses n (total) and k (data) parameters
+            let params =
+                CodeParams::derive_parameters(config.total_shards(), config.data_shards()).unwrap();
+            let shards: Vec<WrappedShard> = params.make_encoder().encode(&data).unwrap();
+            black_box(shards);
+        });
+    }
+
+    #[divan::bench(args = supported_small_configs("novelpoly"))]
+    fn decode_f_missing(bencher: Bencher, config: BenchConfig) {
+        let data = generate_data(config.data_size);
+        let params =
+            CodeParams::derive_parameters(config.total_shards(), config.data_shards()).unwrap();
+        let shards: Vec<Option<WrappedShard>> = params
+            .make_encoder()
+            .encode(&data)
+            .unwrap()
+            .into_iter()
+            .enumerate()
+            .map(|(i, shard)| (i >= config.f).then_some(shard))
+            .collect();
+
+        bencher.bench_local(|| {
+            let recovered =
+                reed_solomon_novelpoly::reconstruct(shards.clone(), config.total_shards()).unwrap();
+            black_box(recovered);
+        });
+    }
+}
+
+// ============================================================================
+// reed-solomon-16 benchmarks
+// ============================================================================
+
+#[cfg(feature = "rs16")]
+#[divan::bench_group(name = "small_rs16")]
+mod small_rs16 {
+    use super::*;
+    use reed_solomon_16::{ReedSolomonDecoder, ReedSolomonEncoder};
+
+    fn original_shards(config: BenchConfig) -> Vec<Vec<u8>> {
+        let data = generate_data(config.data_size);
+        let shard_bytes = config.shard_size(); // Use aligned size for rs16
+
+        let mut original_shards = Vec::new();
+        for i in 0..config.data_shards() {
+            let start = std::cmp::min(i * shard_bytes, data.len());
+            let end = std::cmp::min(start + shard_bytes, data.len());
+            let mut shard = data[start..end].to_vec();
+            shard.resize(shard_bytes, 0);
+            original_shards.push(shard);
+        }
+        original_shards
+    }
+
+    #[divan::bench(args = supported_small_configs("rs16"))]
+    fn setup(bencher: Bencher, config: BenchConfig) {
+        bencher.bench_local(|| {
+            let encoder = ReedSolomonEncoder::new(
+                config.data_shards(),
+                config.coding_shards(),
+                config.shard_size(),
+            )
+            .unwrap();
+            black_box(encoder);
+        });
+    }
+
+    #[divan::bench(args = supported_small_configs("rs16"))]
+    fn encode(bencher: Bencher, config: BenchConfig) {
+        let original_shards = original_shards(config);
+
+        bencher.bench_local(|| {
+            let mut encoder = ReedSolomonEncoder::new(
+                config.data_shards(),
+                config.coding_shards(),
+                config.shard_size(),
+            )
+            .unwrap();
+            for shard in &original_shards {
+                encoder.add_original_shard(shard).unwrap();
+            }
+
+            let result = encoder.encode().unwrap();
+            black_box(result);
+        });
+    }
+
+    #[divan::bench(args = supported_small_configs("rs16"))]
+    fn decode_f_missing(bencher: Bencher, config: BenchConfig) {
+        let original_shards = original_shards(config);
+        let recovery = reed_solomon_16::encode(
+            config.data_shards(),
+            config.coding_shards(),
+            &original_shards,
+        )
+        .unwrap();
+
+        bencher.bench_local(|| {
+            let mut decoder = ReedSolomonDecoder::new(
+                config.data_shards(),
+                config.coding_shards(),
+                config.shard_size(),
+            )
+            .unwrap();
+
+            // Lose the first F originals and replace them with F recovery shards
+            for (index, shard) in original_shards.iter().enumerate().skip(config.f) {
+                decoder.add_original_shard(index, shard).unwrap();
+            }
+            for (index, shard) in recovery.iter().enumerate().take(config.f) {
+                decoder.add_recovery_shard(index, shard).unwrap();
+            }
+
+            let result = decoder.decode().unwrap();
+            black_box(result);
+        });
+    }
+}
+
+// ============================================================================
+// reed-solomon-simd benchmarks
+// ============================================================================
+
+#[cfg(feature = "simd")]
+#[divan::bench_group(name = "small_simd")]
+mod small_simd {
+    use super::*;
+    use reed_solomon_simd::{ReedSolomonDecoder, ReedSolomonEncoder};
+
+    fn original_shards(config: BenchConfig) -> Vec<Vec<u8>> {
+        let data = generate_data(config.data_size);
+        let shard_bytes = config.shard_size(); // Use aligned size for simd
+
+        let mut original_shards = Vec::new();
+        for i in 0..config.data_shards() {
+            let start = std::cmp::min(i * shard_bytes, data.len());
+            let end = std::cmp::min(start + shard_bytes, data.len());
+            let mut shard = data[start..end].to_vec();
+            shard.resize(shard_bytes, 0);
+            original_shards.push(shard);
+        }
+        original_shards
+    }
+
+    #[divan::bench(args = supported_small_configs("simd"))]
+    fn setup(bencher: Bencher, config: BenchConfig) {
+        bencher.bench_local(|| {
+            let encoder = ReedSolomonEncoder::new(
+                config.data_shards(),
+                config.coding_shards(),
+                config.shard_size(),
+            )
+            .unwrap();
+            black_box(encoder);
+        });
+    }
+
+    #[divan::bench(args = supported_small_configs("simd"))]
+    fn encode(bencher: Bencher, config: BenchConfig) {
+        let original_shards = original_shards(config);
+
+        bencher.bench_local(|| {
+            let mut encoder = ReedSolomonEncoder::new(
+                config.data_shards(),
+                config.coding_shards(),
+                config.shard_size(),
+            )
+            .unwrap();
+            for shard in &original_shards {
+                encoder.add_original_shard(shard).unwrap();
+            }
+
+            let result = encoder.encode().unwrap();
+            black_box(result);
+        });
+    }
+
+    #[divan::bench(args = supported_small_configs("simd"))]
+    fn decode_f_missing(bencher: Bencher, config: BenchConfig) {
+        let original_shards = original_shards(config);
+        let recovery = reed_solomon_simd::encode(
+            config.data_shards(),
+            config.coding_shards(),
+            &original_shards,
+        )
+        .unwrap();
+
+        bencher.bench_local(|| {
+            let mut decoder = ReedSolomonDecoder::new(
+                config.data_shards(),
+                config.coding_shards(),
+                config.shard_size(),
+            )
+            .unwrap();
+
+            // Lose the first F originals and replace them with F recovery shards
+            for (index, shard) in original_shards.iter().enumerate().skip(config.f) {
+                decoder.add_original_shard(index, shard).unwrap();
+            }
+            for (index, shard) in recovery.iter().enumerate().take(config.f) {
+                decoder.add_recovery_shard(index, shard).unwrap();
+            }
+
+            let result = decoder.decode().unwrap();
+            black_box(result);
+        });
+    }
+}
@@ -0,0 +1,181 @@
+mod common;
+
+// Only used by the backend groups, each behind its feature
+#[cfg(any(
+    feature = "erasure",
+    feature = "novelpoly",
+    feature = "rs16",
+    feature = "simd"
+))]
+use common::{generate_data, supported_square_widths, SHARE_SIZE};
+#[cfg(any(
+    feature = "erasure",
+    feature = "novelpoly",
+    feature = "rs16",
+    feature = "simd"
+))]
+use divan::{black_box, Bencher};
+#[cfg(any(
+    feature = "erasure",
+    feature = "novelpoly",
+    feature = "rs16",
+    feature = "simd"
+))]
+use reed_solomon_benchmark::codec;
+#[cfg(any(
+    feature = "erasure",
+    feature = "novelpoly",
+    feature = "rs16",
+    feature = "simd"
+))]
+use reed_solomon_benchmark::square::{self, SquareCodec};
+
+fn main() {
+    divan::main();
+}
+
+// Every group takes few samples: at k = 128 one extension runs 384 encodes of
+// 128 + 128 shards, which takes reed-solomon-erasure seconds
+
+/// Times extending a k×k square of shares to 2k×2k.
+#[cfg(any(
+    feature = "erasure",
+    feature = "novelpoly",
+    feature = "rs16",
+    feature = "simd"
+))]
+fn bench_extend(bencher: Bencher, backend: &str, k: usize) {
+    let codec = codec::backend(backend).expect("bench group for a compiled-in backend");
+    let square_codec = SquareCodec::new(codec.as_ref(), k, SHARE_SIZE).unwrap();
+    let shares = square::shares(&generate_data(k * k * SHARE_SIZE), SHARE_SIZE);
+
+    bencher.bench_local(|| {
+        black_box(square_codec.extend(&shares).unwrap());
+    });
+}
+
+/// Times repairing a square of which only the parity quadrant survives:
+/// every right-hand column is rebuilt first, then every row.
+#[cfg(any(
+    feature = "erasure",
+    feature = "novelpoly",
+    feature = "rs16",
+    feature = "simd"
+))]
+fn bench_repair_quadrant(bencher: Bencher, backend: &str, k: usize) {
+    let codec = codec::backend(backend).expect("bench group for a compiled-in backend");
+    let square_codec = SquareCodec::new(codec.as_ref(), k, SHARE_SIZE).unwrap();
+    let shares = square::shares(&generate_data(k * k * SHARE_SIZE), SHARE_SIZE);
+    let square = square_codec.extend(&shares).unwrap();
+    let partial = square.withhold(
+        (0..2 * k)
+            .flat_map(|row| (0..2 * k).map(move |column| (row, column)))
+            .filter(|&(row, column)| row < k || column < k),
+    );
+
+    bencher.bench_local(|| {
+        black_box(square_codec.repair(&partial).unwrap());
+    });
+}
+
+// ============================================================================
+// reed-solomon-erasure benchmarks
+// ============================================================================
+
+#[cfg(feature = "erasure")]
+#[divan::bench_group(name = "square_erasure", sample_count = 3, sample_size = 1)]
+mod square_erasure {
+    use super::*;
+
+    #[divan::bench(args = supported_square_widths("erasure"))]
+    fn extend(bencher: Bencher, k: usize) {
+        bench_extend(bencher, "erasure", k);
+    }
+
+    #[divan::bench(args = supported_square_widths("erasure"))]
+    fn repair_quadrant(bencher: Bencher, k: usize) {
+        bench_repair_quadrant(bencher, "erasure", k);
+    }
+}
+
+// ============================================================================
+// reed-solomon-erasure GF(2^16) benchmarks
+// ============================================================================
+
+#[cfg(feature = "erasure")]
+#[divan::bench_group(name = "square_erasure16", sample_count = 3, sample_size = 1)]
+mod square_erasure16 {
+    use super::*;
+
+    #[divan::bench(args = supported_square_widths("erasure16"))]
+    fn extend(bencher: Bencher, k: usize) {
+        bench_extend(bencher, "erasure16", k);
+    }
+
+    #[divan::bench(args = supported_square_widths("erasure16"))]
+    fn repair_quadrant(bencher: Bencher, k: usize) {
+        bench_repair_quadrant(bencher, "erasure16", k);
+    }
+}
+
+// ============================================================================
+// reed-solomon-novelpoly benchmarks
+// ============================================================================
+
+// Interleaves its input across shards, so every width is reported unsupported
+
+#[cfg(feature = "novelpoly")]
+#[divan::bench_group(name = "square_novelpoly", sample_count = 10, sample_size = 1)]
+mod square_novelpoly {
+    use super::*;
+
+    #[divan::bench(args = supported_square_widths("novelpoly"))]
+    fn extend(bencher: Bencher, k: usize) {
+        bench_extend(bencher, "novelpoly", k);
+    }
+
+    #[divan::bench(args = supported_square_widths("novelpoly"))]
+    fn repair_quadrant(bencher: Bencher, k: usize) {
+        bench_repair_quadrant(bencher, "novelpoly", k);
+    }
+}
+
+// ============================================================================
+// reed-solomon-16 benchmarks
+// ============================================================================
+
+#[cfg(feature = "rs16")]
+#[divan::bench_group(name = "square_rs16", sample_count = 10, sample_size = 1)]
+mod square_rs16 {
+    use super::*;
+
+    #[divan::bench(args = supported_square_widths("rs16"))]
+    fn extend(bencher: Bencher, k: usize) {
+        bench_extend(bencher, "rs16", k);
+    }
+
+    #[divan::bench(args = supported_square_widths("rs16"))]
+    fn repair_quadrant(bencher: Bencher, k: usize) {
+        bench_repair_quadrant(bencher, "rs16", k);
+    }
+}
+
+// ============================================================================
+// reed-solomon-simd benchmarks
+// ============================================================================
+
+#[cfg(feature = "simd")]
+#[divan::bench_group(name = "square_simd", sample_count = 10, sample_size = 1)]
+mod square_simd {
+    use super::*;
+
+    #[divan::bench(args = supported_square_widths("simd"))]
+    fn extend(bencher: Bencher, k: usize) {
+        bench_extend(bencher, "simd", k);
+    }
+
+    #[divan::bench(args = supported_square_widths("simd"))]
+    fn repair_quadrant(bencher: Bencher, k: usize) {
+        bench_repair_quadrant(bencher, "simd", k);
+    }
+}
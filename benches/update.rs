@@ -0,0 +1,142 @@
+mod common;
+
+// Only used by the backend groups, each behind its feature
+#[cfg(any(feature = "erasure", feature = "rs16", feature = "simd"))]
+use common::{generate_data, supported_configs, BenchConfig};
+#[cfg(any(feature = "erasure", feature = "rs16", feature = "simd"))]
+use divan::{black_box, Bencher};
+#[cfg(any(feature = "erasure", feature = "rs16", feature = "simd"))]
+use reed_solomon_benchmark::codec::{self, ErasureCodec};
+
+fn main() {
+    divan::main();
+}
+
+// Rewriting one data shard of a stored object: `update_parity` applies the
+// change to the parity in place, `reencode` is the full encode it replaces.
+// reed-solomon-novelpoly is absent as it only encodes whole payloads.
+
+/// Encoded shards plus a replacement for the middle data shard.
+#[cfg(any(feature = "erasure", feature = "rs16", feature = "simd"))]
+fn rewrite(codec: &dyn ErasureCodec, config: BenchConfig) -> (Vec<Vec<u8>>, usize, Vec<u8>) {
+    let data = generate_data(config.data_size);
+    let shards = codec
+        .encode(config.data_shards(), config.coding_shards(), &data)
+        .unwrap();
+    let index = config.data_shards() / 2;
+    let new = shards[index].iter().map(|byte| !byte).collect();
+    (shards, index, new)
+}
+
+#[cfg(any(feature = "erasure", feature = "rs16", feature = "simd"))]
+fn bench_update_parity(bencher: Bencher, backend: &str, config: BenchConfig) {
+    let codec = codec::backend(backend).expect("bench group for a compiled-in backend");
+    let (k, m) = (config.data_shards(), config.coding_shards());
+    let (shards, index, new) = rewrite(codec.as_ref(), config);
+    let mut parity = shards[k..].to_vec();
+
+    // Applying the same change twice restores the parity, so every iteration
+    // starts from consistent shards without a copy
+    bencher.bench_local(|| {
+        codec
+            .update_parity(k, m, index, &shards[index], &new, &mut parity)
+            .unwrap();
+        black_box(&parity);
+    });
+}
+
+#[cfg(any(feature = "erasure", feature = "rs16", feature = "simd"))]
+fn bench_reencode(bencher: Bencher, backend: &str, config: BenchConfig) {
+    let codec = codec::backend(backend).expect("bench group for a compiled-in backend");
+    let (k, m) = (config.data_shards(), config.coding_shards());
+    let (shards, index, new) = rewrite(codec.as_ref(), config);
+
+    let mut data_shards = shards[..k].to_vec();
+    data_shards[index] = new;
+    let data = data_shards.concat();
+
+    bencher.bench_local(|| {
+        let shards = codec.encode(k, m, &data).unwrap();
+        black_box(shards);
+    });
+}
+
+// ============================================================================
+// reed-solomon-erasure benchmarks
+// ============================================================================
+
+#[cfg(feature = "erasure")]
+#[divan::bench_group(name = "update_erasure")]
+mod update_erasure {
+    use super::*;
+
+    #[divan::bench(args = supported_configs("erasure"))]
+    fn update_parity(bencher: Bencher, config: BenchConfig) {
+        bench_update_parity(bencher, "erasure", config);
+    }
+
+    #[divan::bench(args = supported_configs("erasure"))]
+    fn reencode(bencher: Bencher, config: BenchConfig) {
+        bench_reencode(bencher, "erasure", config);
+    }
+}
+
+// ============================================================================
+// reed-solomon-erasure GF(2^16) benchmarks
+// ============================================================================
+
+#[cfg(feature = "erasure")]
+#[divan::bench_group(name = "update_erasure16")]
+mod update_erasure16 {
+    use super::*;
+
+    #[divan::bench(args = supported_configs("erasure16"))]
+    fn update_parity(bencher: Bencher, config: BenchConfig) {
+        bench_update_parity(bencher, "erasure16", config);
+    }
+
+    #[divan::bench(args = supported_configs("erasure16"))]
+    fn reencode(bencher: Bencher, config: BenchConfig) {
+        bench_reencode(bencher, "erasure16", config);
+    }
+}
+
+// ============================================================================
+// reed-solomon-16 benchmarks
+// ============================================================================
+
+#[cfg(feature = "rs16")]
+#[divan::bench_group(name = "update_rs16")]
+mod update_rs16 {
+    use super::*;
+
+    #[divan::bench(args = supported_configs("rs16"))]
+    fn update_parity(bencher: Bencher, config: BenchConfig) {
+        bench_update_parity(bencher, "rs16", config);
+    }
+
+    #[divan::bench(args = supported_configs("rs16"))]
+    fn reencode(bencher: Bencher, config: BenchConfig) {
+        bench_reencode(bencher, "rs16", config);
+    }
+}
+
+// ============================================================================
+// reed-solomon-simd benchmarks
+// ============================================================================
+
+#[cfg(feature = "simd")]
+#[divan::bench_group(name = "update_simd")]
+mod update_simd {
+    use super::*;
+
+    #[divan::bench(args = supported_configs("simd"))]
+    fn update_parity(bencher: Bencher, config: BenchConfig) {
+        bench_update_parity(bencher, "simd", config);
+    }
+
+    #[divan::bench(args = supported_configs("simd"))]
+    fn reencode(bencher: Bencher, config: BenchConfig) {
+        bench_reencode(bencher, "simd", config);
+    }
+}
@@ -1,6 +1,19 @@
 mod common;
 
-use common::{all_configs, generate_data, BenchConfig};
+// Only used by the backend groups, each behind its feature
+#[cfg(any(
+    feature = "erasure",
+    feature = "novelpoly",
+    feature = "rs16",
+    feature = "simd"
+))]
+use common::{generate_data, supported_configs, BenchConfig};
+#[cfg(any(
+    feature = "erasure",
+    feature = "novelpoly",
+    feature = "rs16",
+    feature = "simd"
+))]
 use divan::{black_box, Bencher};
 
 fn main() {
@@ -11,6 +24,7 @@ fn main() {
 // reed-solomon-erasure benchmarks
 // ============================================================================
 
+#[cfg(feature = "erasure")]
 #[divan::bench_group(name = "verify_erasure")]
 mod verify_erasure {
     use super::*;
@@ -44,7 +58,51 @@ mod verify_erasure {
         });
     }
 
-    #[divan::bench(args = all_configs())]
+    #[divan::bench(args = supported_configs("erasure"))]
+    fn verify(bencher: Bencher, config: BenchConfig) {
+        bench_config(bencher, config);
+    }
+}
+
+// ============================================================================
+// reed-solomon-erasure GF(2^16) benchmarks
+// ============================================================================
+
+#[cfg(feature = "erasure")]
+#[divan::bench_group(name = "verify_erasure16")]
+mod verify_erasure16 {
+    use super::*;
+    use reed_solomon_erasure::galois_16::ReedSolomon;
+
+    fn bench_config(bencher: Bencher, config: BenchConfig) {
+        let data = generate_data(config.data_size);
+        let shard_size = config.shard_size();
+
+        // Prepare and encode shards as GF(2^16) symbols (shard_size is always even)
+        let encoder = ReedSolomon::new(config.data_shards(), config.coding_shards()).unwrap();
+
+        let mut shards: Vec<Vec<[u8; 2]>> = Vec::new();
+        for i in 0..config.data_shards() {
+            let start = i * shard_size;
+            let end = std::cmp::min(start + shard_size, data.len());
+            let mut shard = data[start..end].to_vec();
+            shard.resize(shard_size, 0);
+            shards.push(shard.chunks_exact(2).map(|s| [s[0], s[1]]).collect());
+        }
+
+        for _ in 0..config.coding_shards() {
+            shards.push(vec![[0u8; 2]; shard_size / 2]);
+        }
+
+        encoder.encode(&mut shards).unwrap();
+
+        bencher.bench_local(|| {
+            let result = encoder.verify(&shards).unwrap();
+            black_box(result);
+        });
+    }
+
+    #[divan::bench(args = supported_configs("erasure16"))]
     fn verify(bencher: Bencher, config: BenchConfig) {
         bench_config(bencher, config);
     }
@@ -54,6 +112,7 @@ mod verify_erasure {
 // reed-solomon-simd benchmarks
 // ============================================================================
 
+#[cfg(feature = "novelpoly")]
 #[divan::bench_group(name = "verify_novelpoly")]
 mod verify_novelpoly {
     use super::*;
@@ -76,7 +135,7 @@ mod verify_novelpoly {
         });
     }
 
-    #[divan::bench(args = all_configs())]
+    #[divan::bench(args = supported_configs("novelpoly"))]
     fn verify(bencher: Bencher, config: BenchConfig) {
         bench_config(bencher, config);
     }
@@ -86,6 +145,7 @@ mod verify_novelpoly {
 // reed-solomon-16 benchmarks
 // ============================================================================
 
+#[cfg(feature = "rs16")]
 #[divan::bench_group(name = "verify_rs16")]
 mod verify_rs16 {
     use super::*;
@@ -125,7 +185,7 @@ mod verify_rs16 {
         });
     }
 
-    #[divan::bench(args = all_configs())]
+    #[divan::bench(args = supported_configs("rs16"))]
     fn verify(bencher: Bencher, config: BenchConfig) {
         bench_config(bencher, config);
     }
@@ -135,6 +195,7 @@ mod verify_rs16 {
 // reed-solomon-simd benchmarks
 // ============================================================================
 
+#[cfg(feature = "simd")]
 #[divan::bench_group(name = "verify_simd")]
 mod verify_simd {
     use super::*;
@@ -143,14 +204,6 @@ mod verify_simd {
         let data = generate_data(config.data_size);
         let shard_bytes = config.shard_size(); // Use aligned size for simd
 
-        // Encode data
-        let recovery = reed_solomon_simd::encode(
-            config.data_shards(),
-            config.coding_shards(),
-            &vec![data.clone()],
-        )
-        .unwrap();
-
         // Split data into shards
         let mut original_shards = Vec::new();
         for i in 0..config.data_shards() {
@@ -161,12 +214,20 @@ mod verify_simd {
             original_shards.push(shard);
         }
 
+        // Encode data
+        let recovery = reed_solomon_simd::encode(
+            config.data_shards(),
+            config.coding_shards(),
+            &original_shards,
+        )
+        .unwrap();
+
         bencher.bench_local(|| {
             // Verify by re-encoding and comparing
             let verify_recovery = reed_solomon_simd::encode(
                 config.data_shards(),
                 config.coding_shards(),
-                &vec![data.clone()],
+                &original_shards,
             )
             .unwrap();
             let is_valid = verify_recovery == recovery;
@@ -174,7 +235,7 @@ mod verify_simd {
         });
     }
 
-    #[divan::bench(args = all_configs())]
+    #[divan::bench(args = supported_configs("simd"))]
     fn verify(bencher: Bencher, config: BenchConfig) {
         bench_config(bencher, config);
     }
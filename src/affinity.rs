@@ -0,0 +1,246 @@
+//! Pinning the benchmark thread and checking the CPU for sources of noise.
+//!
+//! Scheduling moves, a power-saving cpufreq governor and a busy SMT sibling
+//! all widen the spread between the fastest and slowest samples. The runner
+//! can pin itself to one core and raise its priority; [`Placement`] records
+//! where it ran and warns about what it could not control.
+
+use std::io;
+use std::time::Duration;
+
+/// How long the sibling cores are watched for load.
+const BUSY_WINDOW: Duration = Duration::from_millis(200);
+
+/// Share of the window above which a sibling counts as busy.
+const BUSY_THRESHOLD: f64 = 0.10;
+
+/// Restricts the calling thread to `core`.
+#[cfg(target_os = "linux")]
+pub fn pin(core: usize) -> io::Result<()> {
+    if core >= libc::CPU_SETSIZE as usize {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("core {} is past the last one the kernel accepts", core),
+        ));
+    }
+    // SAFETY: the set is a plain bitmask on the stack, sized as the call expects
+    unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        libc::CPU_SET(core, &mut set);
+        if libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) != 0 {
+            let err = io::Error::last_os_error();
+            return Err(io::Error::new(
+                err.kind(),
+                format!("core {}: {}", core, err),
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn pin(_core: usize) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "pinning is only implemented for Linux",
+    ))
+}
+
+/// Sets the calling thread's nice value; negative values need `CAP_SYS_NICE`.
+#[cfg(target_os = "linux")]
+pub fn set_nice(nice: i32) -> io::Result<()> {
+    // On Linux the nice value is per thread, and who = 0 is the caller
+    // SAFETY: plain syscall without pointers
+    if unsafe { libc::setpriority(libc::PRIO_PROCESS, 0, nice) } != 0 {
+        let err = io::Error::last_os_error();
+        return Err(io::Error::new(
+            err.kind(),
+            format!("nice {}: {}", nice, err),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn set_nice(_nice: i32) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "priority changes are only implemented for Linux",
+    ))
+}
+
+/// Core the calling thread is running on right now.
+#[cfg(target_os = "linux")]
+pub fn current_cpu() -> Option<usize> {
+    // SAFETY: plain call without arguments
+    usize::try_from(unsafe { libc::sched_getcpu() }).ok()
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn current_cpu() -> Option<usize> {
+    None
+}
+
+/// cpufreq governor of `cpu`, when the kernel exposes one.
+pub fn governor(cpu: usize) -> Option<String> {
+    let path = format!(
+        "/sys/devices/system/cpu/cpu{}/cpufreq/scaling_governor",
+        cpu
+    );
+    Some(std::fs::read_to_string(path).ok()?.trim().to_string())
+}
+
+/// Other hardware threads sharing `cpu`'s core.
+pub fn smt_siblings(cpu: usize) -> Vec<usize> {
+    let path = format!(
+        "/sys/devices/system/cpu/cpu{}/topology/thread_siblings_list",
+        cpu
+    );
+    std::fs::read_to_string(path)
+        .map(|list| parse_cpu_list(&list))
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|&sibling| sibling != cpu)
+        .collect()
+}
+
+/// Parses a kernel CPU list such as `0-3,8`.
+fn parse_cpu_list(list: &str) -> Vec<usize> {
+    let mut cpus = Vec::new();
+    for part in list.trim().split(',').filter(|part| !part.is_empty()) {
+        let bounds = match part.split_once('-') {
+            Some((first, last)) => first.parse().ok().zip(last.parse().ok()),
+            None => part.parse::<usize>().ok().map(|cpu| (cpu, cpu)),
+        };
+        if let Some((first, last)) = bounds {
+            cpus.extend(first..=last);
+        }
+    }
+    cpus
+}
+
+/// Busy and total jiffies of `cpu` from `/proc/stat` contents.
+fn cpu_times(stat: &str, cpu: usize) -> Option<(u64, u64)> {
+    let label = format!("cpu{}", cpu);
+    let line = stat
+        .lines()
+        .find(|line| line.split_whitespace().next() == Some(label.as_str()))?;
+    let fields: Vec<u64> = line
+        .split_whitespace()
+        .skip(1)
+        .map(|field| field.parse().ok())
+        .collect::<Option<_>>()?;
+    let total = fields.iter().sum();
+    // idle and iowait are the 4th and 5th fields
+    let idle = fields.get(3)? + fields.get(4).unwrap_or(&0);
+    Some((total - idle, total))
+}
+
+/// Share of [`BUSY_WINDOW`] each of `cpus` spent busy; `None` where unknown.
+fn busy_shares(cpus: &[usize]) -> Vec<Option<f64>> {
+    // Nothing to sample, so no reason to sleep through the window
+    if cpus.is_empty() {
+        return Vec::new();
+    }
+    let read = || std::fs::read_to_string("/proc/stat").unwrap_or_default();
+    let before = read();
+    std::thread::sleep(BUSY_WINDOW);
+    let after = read();
+    cpus.iter()
+        .map(|&cpu| {
+            let (busy_before, total_before) = cpu_times(&before, cpu)?;
+            let (busy_after, total_after) = cpu_times(&after, cpu)?;
+            let total = total_after.checked_sub(total_before)?.max(1);
+            Some(busy_after.saturating_sub(busy_before) as f64 / total as f64)
+        })
+        .collect()
+}
+
+/// Where the benchmark thread ran and what else shared its core.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Placement {
+    /// Core the thread ran on, or was pinned to.
+    pub cpu: Option<usize>,
+    pub pinned: bool,
+    /// Nice value set with `--nice`; `None` leaves the inherited one.
+    pub nice: Option<i32>,
+    pub governor: Option<String>,
+    /// SMT siblings with the share of the sampling window they were busy.
+    pub siblings: Vec<(usize, Option<f64>)>,
+}
+
+impl Placement {
+    /// Pins to `core` and sets `nice` where given, then surveys the core.
+    pub fn apply(core: Option<usize>, nice: Option<i32>) -> io::Result<Self> {
+        if let Some(core) = core {
+            pin(core)?;
+        }
+        if let Some(nice) = nice {
+            set_nice(nice)?;
+        }
+        let cpu = core.or_else(current_cpu);
+        let siblings = cpu.map(smt_siblings).unwrap_or_default();
+        let busy = busy_shares(&siblings);
+        Ok(Self {
+            cpu,
+            pinned: core.is_some(),
+            nice,
+            governor: cpu.and_then(governor),
+            siblings: siblings.into_iter().zip(busy).collect(),
+        })
+    }
+
+    /// What may have added noise to the samples.
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if !self.pinned {
+            warnings.push("not pinned; the scheduler may move the thread between cores".into());
+        }
+        if let Some(governor) = self.governor.as_deref().filter(|&g| g != "performance") {
+            warnings.push(format!("cpufreq governor is {}, not performance", governor));
+        }
+        for &(sibling, busy) in &self.siblings {
+            if let Some(busy) = busy.filter(|&busy| busy > BUSY_THRESHOLD) {
+                warnings.push(format!(
+                    "SMT sibling cpu{} was {:.0}% busy",
+                    sibling,
+                    busy * 100.0
+                ));
+            }
+        }
+        warnings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_kernel_cpu_lists() {
+        assert_eq!(parse_cpu_list("0-3,8\n"), vec![0, 1, 2, 3, 8]);
+        assert_eq!(parse_cpu_list("5"), vec![5]);
+        assert!(parse_cpu_list("").is_empty());
+
+        let stat = "cpu  10 0 10 80 0 0 0\ncpu0 4 1 5 20 10 0 0\ncpu1 1 0 1 8 0 0 0\n";
+        assert_eq!(cpu_times(stat, 0), Some((10, 40)));
+        assert_eq!(cpu_times(stat, 1), Some((2, 10)));
+        assert_eq!(cpu_times(stat, 2), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn pins_to_the_requested_core() {
+        // A fresh thread, so the test runner's own threads keep their affinity
+        std::thread::spawn(|| {
+            let core = current_cpu().expect("sched_getcpu works on Linux");
+            let placement = Placement::apply(Some(core), None).unwrap();
+            assert_eq!(current_cpu(), Some(core));
+            assert_eq!(placement.cpu, Some(core));
+            assert!(placement.pinned);
+            assert!(pin(usize::MAX).is_err());
+        })
+        .join()
+        .unwrap();
+    }
+}
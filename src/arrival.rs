@@ -0,0 +1,167 @@
+//! Seeded replay of shard arrivals, for decoding as soon as `k` shards are in.
+//!
+//! Arrival times are simulated rather than slept through: the schedule only
+//! decides which shards arrive first, and the decode that follows the `k`-th
+//! arrival is the part timed for real.
+
+use crate::codec::{self, DecoderSession, ErasureCodec};
+use crate::config::BenchConfig;
+use crate::rng::Rng;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Replays run before recording, to settle allocations and branch predictors.
+const WARMUP_REPLAYS: usize = 10;
+
+/// Per-shard network delay: a fixed latency plus uniform jitter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DelayModel {
+    pub base: Duration,
+    pub jitter: Duration,
+}
+
+impl Default for DelayModel {
+    /// A LAN-like 1ms with 1ms of jitter.
+    fn default() -> Self {
+        Self {
+            base: Duration::from_millis(1),
+            jitter: Duration::from_millis(1),
+        }
+    }
+}
+
+/// One shard reaching the decoder `at` after dispersal started.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Arrival {
+    pub index: usize,
+    pub at: Duration,
+}
+
+impl DelayModel {
+    /// Delay of a single message.
+    pub fn sample(&self, rng: &mut Rng) -> Duration {
+        let jitter_ns = self.jitter.as_nanos() as u64;
+        let jitter = if jitter_ns == 0 {
+            0
+        } else {
+            rng.below(jitter_ns + 1)
+        };
+        self.base + Duration::from_nanos(jitter)
+    }
+
+    /// Arrival of each of `total_shards` shards, earliest first.
+    pub fn schedule(&self, total_shards: usize, rng: &mut Rng) -> Vec<Arrival> {
+        let mut arrivals: Vec<Arrival> = (0..total_shards)
+            .map(|index| Arrival {
+                index,
+                at: self.sample(rng),
+            })
+            .collect();
+        // Ties (no jitter) fall back to a random order rather than index order
+        rng.shuffle(&mut arrivals);
+        arrivals.sort_by_key(|arrival| arrival.at);
+        arrivals
+    }
+}
+
+/// Outcome of replaying one arrival schedule.
+#[derive(Debug, Clone, Copy)]
+pub struct Replay {
+    /// Simulated time at which the `k`-th shard arrived.
+    pub kth_arrival: Duration,
+    /// Measured time from the `k`-th arrival until the payload was recovered.
+    pub decode: Duration,
+    /// Data shards absent from the first `k`, which the decode had to restore.
+    pub missing_data_shards: usize,
+}
+
+impl Replay {
+    /// Simulated arrival plus measured decode: when the payload is available.
+    pub fn completion(&self) -> Duration {
+        self.kth_arrival + self.decode
+    }
+}
+
+/// Feeds `shards` to a [`DecoderSession`] in `schedule` order and decodes once it is ready.
+pub fn replay(
+    codec: &dyn ErasureCodec,
+    config: &BenchConfig,
+    shards: &[Vec<u8>],
+    data_len: usize,
+    schedule: &[Arrival],
+) -> codec::Result<Replay> {
+    let (k, m) = (config.data_shards(), config.coding_shards());
+    let mut session = DecoderSession::new(codec, k, m, data_len);
+    for arrival in schedule {
+        let shard = shards[arrival.index].clone();
+        if session.add_shard(arrival.index, shard)? {
+            let start = Instant::now();
+            black_box(session.decode()?);
+            let decode = start.elapsed();
+            return Ok(Replay {
+                kth_arrival: arrival.at,
+                decode,
+                missing_data_shards: session.missing_data_shards(),
+            });
+        }
+    }
+    Err(format!("only {} shards arrived, {} needed", session.received(), k).into())
+}
+
+/// Replays `trials` schedules drawn from `model` with a generator seeded by `seed`.
+pub fn simulate(
+    codec: &dyn ErasureCodec,
+    config: &BenchConfig,
+    data: &[u8],
+    model: &DelayModel,
+    trials: usize,
+    seed: u64,
+) -> codec::Result<Vec<Replay>> {
+    let shards = codec.encode(config.data_shards(), config.coding_shards(), data)?;
+    let mut rng = Rng::new(seed);
+
+    let mut replays = Vec::with_capacity(trials);
+    for trial in 0..WARMUP_REPLAYS + trials {
+        let schedule = model.schedule(config.total_shards(), &mut rng);
+        let replay = replay(codec, config, &shards, data.len(), &schedule)?;
+        if trial >= WARMUP_REPLAYS {
+            replays.push(replay);
+        }
+    }
+    Ok(replays)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schedules_are_seeded_and_sorted() {
+        let model = DelayModel::default();
+        let a = model.schedule(30, &mut Rng::new(5));
+        assert_eq!(a, model.schedule(30, &mut Rng::new(5)));
+        assert_ne!(a, model.schedule(30, &mut Rng::new(6)));
+        assert!(a.windows(2).all(|pair| pair[0].at <= pair[1].at));
+        assert!(a
+            .iter()
+            .all(|arrival| arrival.at >= model.base && arrival.at <= model.base + model.jitter));
+
+        let mut indices: Vec<usize> = a.iter().map(|arrival| arrival.index).collect();
+        indices.sort_unstable();
+        assert_eq!(indices, (0..30).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn decodes_at_the_kth_arrival() {
+        let config = BenchConfig::new(3, 4096);
+        let data = vec![9u8; config.data_size];
+        for codec in codec::backends() {
+            let replays =
+                simulate(codec.as_ref(), &config, &data, &DelayModel::default(), 5, 1).unwrap();
+            assert_eq!(replays.len(), 5, "{}", codec.name());
+            assert!(replays
+                .iter()
+                .all(|replay| replay.missing_data_shards <= config.data_shards()));
+        }
+    }
+}
@@ -0,0 +1,608 @@
+//! In-process simulation of erasure-coded Byzantine reliable broadcast.
+//!
+//! Follows the AVID-style protocol of Cachin and Tessaro: the sender encodes
+//! the payload into one shard per node, `n = 3F` for a [`BenchConfig`] plus
+//! any [extra nodes](Scenario::extra_nodes), and sends shard `j` to node `j`
+//! along with the [Merkle root](crate::commitment) of all shards and the
+//! shard's inclusion proof. Each node echoes its shard and proof to everyone;
+//! `n - F` valid echoes (or `F + 1` readies) make a node send READY, and
+//! `n - F` readies plus `k = F` valid shards let it decode. A decoded payload
+//! is re-encoded and its root checked against the one committed to, so an
+//! inconsistent dispersal delivers nothing rather than diverging payloads.
+//!
+//! The textbook protocol needs `n = 3F + 1`. With `n = 3F`, two quorums of
+//! `n - F` may overlap in faulty nodes only. An [equivocating](Sender::Equivocate)
+//! sender backed by [colluding](Fault::Collude) nodes then gets each half of
+//! the honest nodes to deliver a different payload; one extra node restores
+//! agreement.
+//!
+//! Message delays run on a simulated clock; encoding, decoding and hashing are
+//! real, and the first two are timed to give the coding share of the CPU time
+//! spent per broadcast.
+
+use crate::arrival::DelayModel;
+use crate::codec::{self, ErasureCodec};
+use crate::commitment::{Digest, MerkleTree, Proof};
+use crate::config::BenchConfig;
+use crate::rng::Rng;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use std::fmt;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+/// Extra delay a [`Fault::Delay`] node adds to all it sends, in multiples of
+/// the model's worst-case delay.
+const DELAY_FACTOR: u32 = 20;
+
+/// How a faulty node deviates from the protocol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fault {
+    /// Crashes: sends nothing at all.
+    Drop,
+    /// Follows the protocol, but everything it sends arrives late.
+    Delay,
+    /// Follows the protocol, but echoes a corrupted shard.
+    Corrupt,
+    /// Faulty nodes take turns at dropping, delaying and corrupting.
+    Mixed,
+    /// Backs an equivocating sender: echoes and readies every root it is
+    /// sent, but only to the nodes the sender gave the same root.
+    Collude,
+}
+
+impl Fault {
+    pub const ALL: &'static [Fault] = &[
+        Fault::Drop,
+        Fault::Delay,
+        Fault::Corrupt,
+        Fault::Mixed,
+        Fault::Collude,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Fault::Drop => "drop",
+            Fault::Delay => "delay",
+            Fault::Corrupt => "corrupt",
+            Fault::Mixed => "mixed",
+            Fault::Collude => "collude",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|fault| fault.name() == name)
+    }
+
+    /// Behaviour of the `nth` faulty node.
+    fn of_node(self, nth: usize) -> Self {
+        match self {
+            Fault::Mixed => [Fault::Drop, Fault::Delay, Fault::Corrupt][nth % 3],
+            fault => fault,
+        }
+    }
+}
+
+impl fmt::Display for Fault {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// How the sender disperses the payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sender {
+    /// Sends every node its shard of one codeword.
+    Honest,
+    /// Encodes a second payload and sends half the honest nodes shards of
+    /// each, under two roots; faulty nodes are sent both.
+    Equivocate,
+}
+
+impl Sender {
+    pub const ALL: &'static [Sender] = &[Sender::Honest, Sender::Equivocate];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Sender::Honest => "honest",
+            Sender::Equivocate => "equivocate",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|sender| sender.name() == name)
+    }
+}
+
+impl fmt::Display for Sender {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Which nodes misbehave and how messages are delayed.
+#[derive(Debug, Clone, Copy)]
+pub struct Scenario {
+    pub sender: Sender,
+    /// Nodes beyond the `3F` of the configuration, each holding one more
+    /// parity shard; one gives the textbook `n = 3F + 1`.
+    pub extra_nodes: usize,
+    /// Number of faulty nodes; the protocol guarantees hold up to F.
+    pub faulty: usize,
+    pub fault: Fault,
+    pub delays: DelayModel,
+    /// Picks the faulty nodes and draws every message delay.
+    pub seed: u64,
+}
+
+/// Result of one broadcast.
+#[derive(Debug, Clone, Copy)]
+pub struct Outcome {
+    pub honest: usize,
+    /// Honest nodes that delivered a payload.
+    pub delivered: usize,
+    /// Whether every delivering honest node output the same payload, and the
+    /// sender's one if the sender was honest.
+    pub agreed: bool,
+    /// Simulated time of the last honest delivery.
+    pub completion: Duration,
+    pub messages: usize,
+    /// Shards dropped because their inclusion proof did not verify.
+    pub rejected: usize,
+    /// Time spent in `encode` and `decode`, by the sender and every honest node.
+    pub coding: Duration,
+    /// Time spent running the whole simulation on one thread.
+    pub total: Duration,
+}
+
+impl Outcome {
+    /// Safety: no two honest nodes delivered different payloads, and none
+    /// delivered anything but an honest sender's payload.
+    pub fn is_safe(&self) -> bool {
+        self.agreed
+    }
+
+    /// Liveness: every honest node delivered.
+    pub fn is_live(&self) -> bool {
+        self.delivered == self.honest
+    }
+
+    /// Fraction of the simulation's CPU time spent encoding and decoding.
+    pub fn coding_share(&self) -> f64 {
+        self.coding.as_secs_f64() / self.total.as_secs_f64().max(f64::MIN_POSITIVE)
+    }
+}
+
+enum Body {
+    /// Shard `to` from the sender.
+    Send(Rc<Vec<u8>>, Rc<Proof>),
+    /// The sending node's own shard.
+    Echo(Rc<Vec<u8>>, Rc<Proof>),
+    Ready,
+}
+
+struct Message {
+    at: Duration,
+    /// Breaks ties in `at` by send order, keeping runs deterministic.
+    seq: u64,
+    from: usize,
+    to: usize,
+    root: Digest,
+    body: Body,
+}
+
+impl PartialEq for Message {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Message {}
+
+impl PartialOrd for Message {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Message {
+    /// Reversed, so the max-heap pops the earliest message first.
+    fn cmp(&self, other: &Self) -> Ordering {
+        (other.at, other.seq).cmp(&(self.at, self.seq))
+    }
+}
+
+/// A node's view of one dispersal, keyed by its Merkle root.
+struct Tally {
+    shards: Vec<Option<Rc<Vec<u8>>>>,
+    valid: usize,
+    readies: Vec<bool>,
+    ready_count: usize,
+}
+
+impl Tally {
+    fn new(total: usize) -> Self {
+        Self {
+            shards: vec![None; total],
+            valid: 0,
+            readies: vec![false; total],
+            ready_count: 0,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Node {
+    fault: Option<Fault>,
+    echoed: bool,
+    readied: bool,
+    /// `Some(None)` when the dispersal turned out inconsistent.
+    output: Option<Option<Vec<u8>>>,
+    tallies: HashMap<Digest, Tally>,
+}
+
+struct Network<'a> {
+    codec: &'a dyn ErasureCodec,
+    /// Faults tolerated.
+    f: usize,
+    data_shards: usize,
+    parity_shards: usize,
+    data_len: usize,
+    delays: DelayModel,
+    /// Root the sender gave each honest node, so colluders can keep each half
+    /// of an equivocation unaware of the other.
+    dispersed: Vec<Option<Digest>>,
+    rng: Rng,
+    queue: BinaryHeap<Message>,
+    now: Duration,
+    seq: u64,
+    messages: usize,
+    rejected: usize,
+    coding: Duration,
+}
+
+impl Network<'_> {
+    fn total(&self) -> usize {
+        self.data_shards + self.parity_shards
+    }
+
+    /// Echoes or readies that every honest node can gather.
+    fn quorum(&self) -> usize {
+        self.total() - self.f
+    }
+
+    fn send(&mut self, from: usize, to: usize, root: &Digest, body: Body, extra: Duration) {
+        let delay = if from == to {
+            Duration::ZERO
+        } else {
+            self.delays.sample(&mut self.rng)
+        };
+        self.seq += 1;
+        self.messages += 1;
+        self.queue.push(Message {
+            at: self.now + delay + extra,
+            seq: self.seq,
+            from,
+            to,
+            root: *root,
+            body,
+        });
+    }
+
+    fn broadcast(&mut self, node: &Node, from: usize, root: &Digest, body: impl Fn() -> Body) {
+        if node.fault == Some(Fault::Drop) {
+            return;
+        }
+        let extra = if node.fault == Some(Fault::Delay) {
+            (self.delays.base + self.delays.jitter) * DELAY_FACTOR
+        } else {
+            Duration::ZERO
+        };
+        for to in 0..self.total() {
+            self.send(from, to, root, body(), extra);
+        }
+    }
+
+    fn handle(&mut self, node: &mut Node, id: usize, message: Message) -> codec::Result<()> {
+        if node.fault == Some(Fault::Drop) {
+            return Ok(());
+        }
+        let total = self.total();
+        let root = message.root;
+        if node.fault == Some(Fault::Collude) {
+            // Vouches for whatever the sender hands it, and ignores the rest
+            if let Body::Send(shard, proof) = message.body {
+                if proof.index == id && proof.verify(&root, total, &shard) {
+                    for to in 0..total {
+                        if self.dispersed[to].is_none_or(|dispersed| dispersed == root) {
+                            let echo = Body::Echo(shard.clone(), proof.clone());
+                            self.send(id, to, &root, echo, Duration::ZERO);
+                            self.send(id, to, &root, Body::Ready, Duration::ZERO);
+                        }
+                    }
+                }
+            }
+            return Ok(());
+        }
+        match message.body {
+            Body::Send(shard, proof) => {
+                if node.echoed || proof.index != id || !proof.verify(&root, total, &shard) {
+                    return Ok(());
+                }
+                node.echoed = true;
+                let shard = match node.fault {
+                    Some(Fault::Corrupt) => {
+                        let mut corrupted = shard.as_ref().clone();
+                        let at = self.rng.below(corrupted.len().max(1) as u64) as usize;
+                        if let Some(byte) = corrupted.get_mut(at) {
+                            *byte ^= 0xFF;
+                        }
+                        Rc::new(corrupted)
+                    }
+                    _ => shard,
+                };
+                self.broadcast(node, id, &root, || Body::Echo(shard.clone(), proof.clone()));
+            }
+            Body::Echo(shard, proof) => {
+                if proof.index != message.from || !proof.verify(&root, total, &shard) {
+                    self.rejected += 1;
+                    return Ok(());
+                }
+                let tally = node
+                    .tallies
+                    .entry(root)
+                    .or_insert_with(|| Tally::new(total));
+                if tally.shards[message.from].is_none() {
+                    tally.shards[message.from] = Some(shard);
+                    tally.valid += 1;
+                }
+                if tally.valid >= self.quorum() && !node.readied {
+                    node.readied = true;
+                    self.broadcast(node, id, &root, || Body::Ready);
+                }
+            }
+            Body::Ready => {
+                let tally = node
+                    .tallies
+                    .entry(root)
+                    .or_insert_with(|| Tally::new(total));
+                if !tally.readies[message.from] {
+                    tally.readies[message.from] = true;
+                    tally.ready_count += 1;
+                }
+                // Amplification: F + 1 readies include an honest one
+                if tally.ready_count > self.f && !node.readied {
+                    node.readied = true;
+                    self.broadcast(node, id, &root, || Body::Ready);
+                }
+            }
+        }
+        // Delivering sends nothing, so faulty nodes skip it and stay out of the coding time
+        if node.fault.is_some() {
+            return Ok(());
+        }
+        self.try_deliver(node, &root)
+    }
+
+    /// Decodes once `n - F` readies and `k` valid shards are in.
+    fn try_deliver(&mut self, node: &mut Node, root: &Digest) -> codec::Result<()> {
+        let (k, m) = (self.data_shards, self.parity_shards);
+        let Some(tally) = node.tallies.get(root) else {
+            return Ok(());
+        };
+        if node.output.is_some() || tally.ready_count < self.quorum() || tally.valid < k {
+            return Ok(());
+        }
+        let mut shards: Vec<Option<Vec<u8>>> = vec![None; k + m];
+        for (slot, shard) in shards
+            .iter_mut()
+            .zip(&tally.shards)
+            .filter(|(_, shard)| shard.is_some())
+            .take(k)
+        {
+            *slot = shard.as_ref().map(|shard| shard.as_ref().clone());
+        }
+
+        let start = Instant::now();
+        let payload = self.codec.decode(k, m, &shards, self.data_len)?;
+        let reencoded = self.codec.encode(k, m, &payload)?;
+        self.coding += start.elapsed();
+
+        let consistent = MerkleTree::new(&reencoded).root() == *root;
+        node.output = Some(consistent.then_some(payload));
+        Ok(())
+    }
+}
+
+/// Broadcasts `data` to `config.total_shards()` nodes plus the scenario's extra ones.
+pub fn simulate(
+    codec: &dyn ErasureCodec,
+    config: &BenchConfig,
+    data: &[u8],
+    scenario: &Scenario,
+) -> codec::Result<Outcome> {
+    let started = Instant::now();
+    let (k, m) = (
+        config.data_shards(),
+        config.coding_shards() + scenario.extra_nodes,
+    );
+    let total = k + m;
+    if scenario.faulty > total {
+        return Err(format!("{} faulty nodes among {}", scenario.faulty, total).into());
+    }
+
+    let mut rng = Rng::new(scenario.seed);
+    let mut order: Vec<usize> = (0..total).collect();
+    rng.shuffle(&mut order);
+    let mut nodes: Vec<Node> = (0..total).map(|_| Node::default()).collect();
+    for (nth, &id) in order[..scenario.faulty].iter().enumerate() {
+        nodes[id].fault = Some(scenario.fault.of_node(nth));
+    }
+
+    let mut network = Network {
+        codec,
+        f: config.f,
+        data_shards: k,
+        parity_shards: m,
+        data_len: data.len(),
+        delays: scenario.delays,
+        dispersed: vec![None; total],
+        rng,
+        queue: BinaryHeap::new(),
+        now: Duration::ZERO,
+        seq: 0,
+        messages: 0,
+        rejected: 0,
+        coding: Duration::ZERO,
+    };
+
+    // An equivocating sender also disperses the payload with every byte flipped
+    let mut payloads = vec![data.to_vec()];
+    if scenario.sender == Sender::Equivocate {
+        payloads.push(data.iter().map(|byte| !byte).collect());
+    }
+    let mut dispersals = Vec::new();
+    for payload in &payloads {
+        let start = Instant::now();
+        let shards = codec.encode(k, m, payload)?;
+        network.coding += start.elapsed();
+        let tree = MerkleTree::new(&shards);
+        dispersals.push((shards, tree));
+    }
+
+    // Honest nodes are split in half between the dispersals; faulty ones get all
+    let honest_ids: Vec<usize> = (0..total).filter(|&id| nodes[id].fault.is_none()).collect();
+    let half = honest_ids.len().div_ceil(dispersals.len());
+    for to in 0..total {
+        let which = match honest_ids.iter().position(|&id| id == to) {
+            Some(nth) => nth / half..nth / half + 1,
+            None => 0..dispersals.len(),
+        };
+        if which.len() == 1 {
+            network.dispersed[to] = Some(dispersals[which.start].1.root());
+        }
+        for (shards, tree) in &dispersals[which] {
+            let proof = tree.proof(to).expect("one leaf per shard");
+            let body = Body::Send(Rc::new(shards[to].clone()), Rc::new(proof));
+            // The sender is not one of the nodes; `from` only matters between nodes
+            network.send(total, to, &tree.root(), body, Duration::ZERO);
+        }
+    }
+
+    let mut completion = Duration::ZERO;
+    while let Some(message) = network.queue.pop() {
+        network.now = message.at;
+        let id = message.to;
+        let node = &mut nodes[id];
+        let delivered = node.output.is_some();
+        network.handle(node, id, message)?;
+        if !delivered && node.output.is_some() && node.fault.is_none() {
+            completion = network.now;
+        }
+    }
+
+    let honest = nodes.iter().filter(|node| node.fault.is_none());
+    let outputs: Vec<_> = honest
+        .clone()
+        .filter_map(|node| node.output.as_ref())
+        .collect();
+    let agreed = match scenario.sender {
+        Sender::Honest => outputs.iter().all(|output| output.as_deref() == Some(data)),
+        Sender::Equivocate => {
+            let mut delivered = outputs.iter().copied().flatten();
+            let first = delivered.next();
+            delivered.all(|payload| Some(payload) == first)
+        }
+    };
+    Ok(Outcome {
+        honest: honest.count(),
+        delivered: outputs.iter().filter(|output| output.is_some()).count(),
+        agreed,
+        completion,
+        messages: network.messages,
+        rejected: network.rejected,
+        coding: network.coding,
+        total: started.elapsed(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scenario(faulty: usize, fault: Fault) -> Scenario {
+        Scenario {
+            sender: Sender::Honest,
+            extra_nodes: 0,
+            faulty,
+            fault,
+            delays: DelayModel::default(),
+            seed: 3,
+        }
+    }
+
+    #[test]
+    fn safe_and_live_with_up_to_f_faults() {
+        let config = BenchConfig::new(2, 4096);
+        let data: Vec<u8> = (0..config.data_size).map(|i| (i % 251) as u8).collect();
+        for codec in codec::backends() {
+            for &fault in Fault::ALL {
+                let outcome =
+                    simulate(codec.as_ref(), &config, &data, &scenario(config.f, fault)).unwrap();
+                let label = format!("{} {}", codec.name(), fault);
+                assert!(outcome.is_safe() && outcome.is_live(), "{}", label);
+                assert_eq!(
+                    outcome.honest,
+                    config.total_shards() - config.f,
+                    "{}",
+                    label
+                );
+                assert!(outcome.coding_share() > 0.0 && outcome.coding_share() <= 1.0);
+                if fault == Fault::Corrupt {
+                    assert!(outcome.rejected > 0, "{}", label);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn equivocation_splits_3f_nodes_but_not_3f_plus_1() {
+        let config = BenchConfig::new(2, 4096);
+        let data: Vec<u8> = (0..config.data_size).map(|i| (i % 251) as u8).collect();
+        for codec in codec::backends() {
+            for extra_nodes in [0, 1] {
+                let scenario = Scenario {
+                    sender: Sender::Equivocate,
+                    extra_nodes,
+                    ..scenario(config.f, Fault::Collude)
+                };
+                let outcome = simulate(codec.as_ref(), &config, &data, &scenario).unwrap();
+                let label = format!("{} n=3F+{}", codec.name(), extra_nodes);
+                // At 3F each half gathers a quorum with the colluders' help
+                assert_eq!(outcome.is_safe(), extra_nodes == 1, "{}", label);
+                assert!(outcome.is_live(), "{}", label);
+            }
+        }
+    }
+
+    #[test]
+    fn stalls_without_diverging_beyond_f_crashes() {
+        let config = BenchConfig::new(2, 4096);
+        let data = vec![5u8; config.data_size];
+        for codec in codec::backends() {
+            let outcome = simulate(
+                codec.as_ref(),
+                &config,
+                &data,
+                &scenario(config.f + 1, Fault::Drop),
+            )
+            .unwrap();
+            // 2F - 1 honest nodes cannot gather 2F echoes, so nobody delivers
+            assert!(outcome.is_safe() && !outcome.is_live(), "{}", codec.name());
+            assert_eq!(outcome.delivered, 0, "{}", codec.name());
+        }
+    }
+}
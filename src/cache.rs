@@ -0,0 +1,89 @@
+//! Cache eviction between samples, so inputs arrive cold as they do off the network.
+
+use std::hint::black_box;
+
+/// Assumed last-level cache size when sysfs does not report one.
+const FALLBACK_LLC_BYTES: usize = 32 * 1024 * 1024;
+
+/// Stride used when touching the scrub buffer; one write per cache line.
+const CACHE_LINE: usize = 64;
+
+/// Largest last-level cache reported for cpu0, in bytes.
+///
+/// Falls back to [`FALLBACK_LLC_BYTES`] off Linux or when sysfs is unavailable.
+pub fn llc_size() -> usize {
+    let sizes = (0..8).filter_map(|index| {
+        let path = format!("/sys/devices/system/cpu/cpu0/cache/index{}/size", index);
+        parse_cache_size(std::fs::read_to_string(path).ok()?.trim())
+    });
+    sizes.max().unwrap_or(FALLBACK_LLC_BYTES)
+}
+
+/// Parses sysfs sizes such as `32K`, `2048K` or `32M`.
+fn parse_cache_size(size: &str) -> Option<usize> {
+    let (digits, unit) = size.split_at(size.find(|c: char| !c.is_ascii_digit())?);
+    let value: usize = digits.parse().ok()?;
+    match unit {
+        "K" => Some(value * 1024),
+        "M" => Some(value * 1024 * 1024),
+        "G" => Some(value * 1024 * 1024 * 1024),
+        _ => None,
+    }
+}
+
+/// A buffer larger than the last-level cache, streamed through to evict it.
+pub struct Scrubber {
+    buffer: Vec<u8>,
+    pass: u8,
+}
+
+impl Scrubber {
+    /// Scrub buffer of twice the last-level cache size.
+    pub fn for_llc() -> Self {
+        Self::with_size(2 * llc_size())
+    }
+
+    pub fn with_size(bytes: usize) -> Self {
+        Self {
+            buffer: vec![0u8; bytes.max(CACHE_LINE)],
+            pass: 0,
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Writes every cache line of the buffer, displacing whatever was cached.
+    ///
+    /// Writes rather than reads, so the evicted lines are replaced by dirty
+    /// ones and the next access to the old data has to go to memory.
+    pub fn scrub(&mut self) {
+        self.pass = self.pass.wrapping_add(1);
+        for line in self.buffer.chunks_mut(CACHE_LINE) {
+            line[0] = self.pass;
+        }
+        black_box(&self.buffer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_sysfs_sizes() {
+        assert_eq!(parse_cache_size("48K"), Some(48 * 1024));
+        assert_eq!(parse_cache_size("32M"), Some(32 * 1024 * 1024));
+        assert_eq!(parse_cache_size("1024"), None);
+        assert_eq!(parse_cache_size("K"), None);
+    }
+
+    #[test]
+    fn scrub_touches_every_line() {
+        let mut scrubber = Scrubber::with_size(4 * CACHE_LINE + 1);
+        scrubber.scrub();
+        scrubber.scrub();
+        assert!(scrubber.buffer.chunks(CACHE_LINE).all(|line| line[0] == 2));
+    }
+}
@@ -0,0 +1,382 @@
+//! reed-solomon-erasure adapters over GF(2^8) and GF(2^16).
+
+use super::{
+    aligned_shard_size, check_all_arrived, check_arrival, check_indices, check_shards, restore_len,
+    shard_delta, split_shards, xor_into, Capabilities, CodecError, ErasureCodec, ErrorKind, Result,
+};
+use reed_solomon_erasure::{galois_16, galois_8, ShardByShard};
+
+/// GF(2^8) has 256 elements, one evaluation point per shard.
+const GALOIS_8_CAPABILITIES: Capabilities = Capabilities {
+    max_data_shards: 255,
+    max_parity_shards: 255,
+    max_total_shards: 256,
+    shard_alignment: 1,
+    min_shard_size: 1,
+};
+
+/// `reed_solomon_erasure::galois_8`, limited to 256 total shards.
+pub struct Erasure;
+
+/// `reed_solomon_erasure::galois_16`, for shard counts beyond 256.
+pub struct Erasure16;
+
+impl ErasureCodec for Erasure {
+    fn name(&self) -> &'static str {
+        "erasure"
+    }
+
+    fn label(&self) -> &'static str {
+        // Cargo unifies features, so this holds for every galois_8 call in the build
+        if cfg!(feature = "erasure-simd") {
+            "erasure[simd-accel]"
+        } else {
+            "erasure"
+        }
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        GALOIS_8_CAPABILITIES
+    }
+
+    fn encode(
+        &self,
+        data_shards: usize,
+        parity_shards: usize,
+        data: &[u8],
+    ) -> Result<Vec<Vec<u8>>> {
+        let encoder =
+            galois_8::ReedSolomon::new(data_shards, parity_shards).map_err(unsupported)?;
+        let shard_size = aligned_shard_size(data.len(), data_shards);
+
+        let mut shards = split_shards(data, data_shards, shard_size);
+        shards.resize(data_shards + parity_shards, vec![0u8; shard_size]);
+        encoder.encode(&mut shards)?;
+        Ok(shards)
+    }
+
+    fn decode(
+        &self,
+        data_shards: usize,
+        parity_shards: usize,
+        shards: &[Option<Vec<u8>>],
+        data_len: usize,
+    ) -> Result<Vec<u8>> {
+        check_shards(&self.capabilities(), data_shards, parity_shards, shards)?;
+        let decoder =
+            galois_8::ReedSolomon::new(data_shards, parity_shards).map_err(unsupported)?;
+
+        let mut shards = shards.to_vec();
+        decoder.reconstruct_data(&mut shards)?;
+        let data = shards
+            .into_iter()
+            .take(data_shards)
+            .flat_map(|shard| shard.expect("reconstruct_data restores every data shard"))
+            .collect();
+        restore_len(data, data_len)
+    }
+
+    fn reconstruct(
+        &self,
+        data_shards: usize,
+        parity_shards: usize,
+        shards: &[Option<Vec<u8>>],
+    ) -> Result<Vec<Vec<u8>>> {
+        check_shards(&self.capabilities(), data_shards, parity_shards, shards)?;
+        let decoder =
+            galois_8::ReedSolomon::new(data_shards, parity_shards).map_err(unsupported)?;
+
+        let mut shards = shards.to_vec();
+        decoder.reconstruct(&mut shards)?;
+        Ok(shards
+            .into_iter()
+            .map(|shard| shard.expect("reconstruct restores every shard"))
+            .collect())
+    }
+
+    fn repair(
+        &self,
+        data_shards: usize,
+        parity_shards: usize,
+        shards: &[Option<Vec<u8>>],
+        missing: &[usize],
+    ) -> Result<Vec<Vec<u8>>> {
+        check_indices(missing, data_shards + parity_shards)?;
+        check_shards(&self.capabilities(), data_shards, parity_shards, shards)?;
+        let decoder =
+            galois_8::ReedSolomon::new(data_shards, parity_shards).map_err(unsupported)?;
+
+        // Absent parity is only recomputed when some of it was asked for
+        let mut shards = shards.to_vec();
+        if missing.iter().all(|&index| index < data_shards) {
+            decoder.reconstruct_data(&mut shards)?;
+        } else {
+            decoder.reconstruct(&mut shards)?;
+        }
+        Ok(missing
+            .iter()
+            .map(|&index| {
+                shards[index]
+                    .clone()
+                    .expect("requested shards are restored")
+            })
+            .collect())
+    }
+
+    fn update_parity(
+        &self,
+        data_shards: usize,
+        parity_shards: usize,
+        index: usize,
+        old: &[u8],
+        new: &[u8],
+        parity: &mut [Vec<u8>],
+    ) -> Result<()> {
+        let encoder =
+            galois_8::ReedSolomon::new(data_shards, parity_shards).map_err(unsupported)?;
+        let delta = shard_delta(data_shards, parity_shards, index, old, new, parity)?;
+
+        // encode_single_sep accumulates into the parity for every shard but the
+        // first, which it overwrites; shard 0's delta goes through a scratch copy
+        if index > 0 {
+            encoder.encode_single_sep(index, &delta, parity)?;
+        } else {
+            let mut scratch = vec![vec![0u8; delta.len()]; parity_shards];
+            encoder.encode_single_sep(index, &delta, &mut scratch)?;
+            for (shard, change) in parity.iter_mut().zip(&scratch) {
+                xor_into(shard, change);
+            }
+        }
+        Ok(())
+    }
+    fn encode_incremental(
+        &self,
+        data_shards: usize,
+        parity_shards: usize,
+        arrivals: &mut dyn Iterator<Item = Vec<u8>>,
+    ) -> Result<Vec<Vec<u8>>> {
+        let encoder =
+            galois_8::ReedSolomon::new(data_shards, parity_shards).map_err(unsupported)?;
+        let mut sbs = ShardByShard::new(&encoder);
+
+        // Every slot must be allocated at the shard size, known from the first arrival
+        let mut shards: Vec<Vec<u8>> = Vec::new();
+        for (index, shard) in arrivals.enumerate() {
+            if shards.is_empty() {
+                shards = vec![vec![0u8; shard.len()]; data_shards + parity_shards];
+            }
+            check_arrival(data_shards, index, shard.len(), shards[0].len())?;
+            shards[index] = shard;
+            sbs.encode(&mut shards)?;
+        }
+        check_all_arrived(data_shards, sbs.cur_input_index())?;
+        Ok(shards)
+    }
+}
+
+impl ErasureCodec for Erasure16 {
+    fn name(&self) -> &'static str {
+        "erasure16"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            max_data_shards: 65535,
+            max_parity_shards: 65535,
+            max_total_shards: 65536,
+            shard_alignment: 2,
+            min_shard_size: 2,
+        }
+    }
+
+    fn encode(
+        &self,
+        data_shards: usize,
+        parity_shards: usize,
+        data: &[u8],
+    ) -> Result<Vec<Vec<u8>>> {
+        let encoder =
+            galois_16::ReedSolomon::new(data_shards, parity_shards).map_err(unsupported)?;
+        let shard_size = aligned_shard_size(data.len(), data_shards);
+
+        // shard_size is a multiple of 64, so every shard splits into whole symbols
+        let mut shards: Vec<Vec<[u8; 2]>> = split_shards(data, data_shards, shard_size)
+            .iter()
+            .map(|shard| to_symbols(shard))
+            .collect();
+        shards.resize(data_shards + parity_shards, vec![[0u8; 2]; shard_size / 2]);
+        encoder.encode(&mut shards)?;
+        Ok(shards.iter().map(|shard| shard.concat()).collect())
+    }
+
+    fn decode(
+        &self,
+        data_shards: usize,
+        parity_shards: usize,
+        shards: &[Option<Vec<u8>>],
+        data_len: usize,
+    ) -> Result<Vec<u8>> {
+        check_shards(&self.capabilities(), data_shards, parity_shards, shards)?;
+        let decoder =
+            galois_16::ReedSolomon::new(data_shards, parity_shards).map_err(unsupported)?;
+
+        let mut shards: Vec<Option<Vec<[u8; 2]>>> = shards
+            .iter()
+            .map(|shard| shard.as_deref().map(to_symbols))
+            .collect();
+        decoder.reconstruct_data(&mut shards)?;
+        let data = shards
+            .into_iter()
+            .take(data_shards)
+            .flat_map(|shard| {
+                shard
+                    .expect("reconstruct_data restores every data shard")
+                    .concat()
+            })
+            .collect();
+        restore_len(data, data_len)
+    }
+
+    fn reconstruct(
+        &self,
+        data_shards: usize,
+        parity_shards: usize,
+        shards: &[Option<Vec<u8>>],
+    ) -> Result<Vec<Vec<u8>>> {
+        check_shards(&self.capabilities(), data_shards, parity_shards, shards)?;
+        let decoder =
+            galois_16::ReedSolomon::new(data_shards, parity_shards).map_err(unsupported)?;
+
+        let mut shards: Vec<Option<Vec<[u8; 2]>>> = shards
+            .iter()
+            .map(|shard| shard.as_deref().map(to_symbols))
+            .collect();
+        decoder.reconstruct(&mut shards)?;
+        Ok(shards
+            .into_iter()
+            .map(|shard| shard.expect("reconstruct restores every shard").concat())
+            .collect())
+    }
+
+    fn repair(
+        &self,
+        data_shards: usize,
+        parity_shards: usize,
+        shards: &[Option<Vec<u8>>],
+        missing: &[usize],
+    ) -> Result<Vec<Vec<u8>>> {
+        check_indices(missing, data_shards + parity_shards)?;
+        check_shards(&self.capabilities(), data_shards, parity_shards, shards)?;
+        let decoder =
+            galois_16::ReedSolomon::new(data_shards, parity_shards).map_err(unsupported)?;
+
+        // Absent parity is only recomputed when some of it was asked for
+        let mut shards: Vec<Option<Vec<[u8; 2]>>> = shards
+            .iter()
+            .map(|shard| shard.as_deref().map(to_symbols))
+            .collect();
+        if missing.iter().all(|&index| index < data_shards) {
+            decoder.reconstruct_data(&mut shards)?;
+        } else {
+            decoder.reconstruct(&mut shards)?;
+        }
+        Ok(missing
+            .iter()
+            .map(|&index| {
+                shards[index]
+                    .as_ref()
+                    .expect("requested shards are restored")
+                    .concat()
+            })
+            .collect())
+    }
+
+    fn update_parity(
+        &self,
+        data_shards: usize,
+        parity_shards: usize,
+        index: usize,
+        old: &[u8],
+        new: &[u8],
+        parity: &mut [Vec<u8>],
+    ) -> Result<()> {
+        let encoder =
+            galois_16::ReedSolomon::new(data_shards, parity_shards).map_err(unsupported)?;
+        let delta = to_symbols(&shard_delta(
+            data_shards,
+            parity_shards,
+            index,
+            old,
+            new,
+            parity,
+        )?);
+
+        // The parity is held as bytes, so the change is encoded into zeroed
+        // symbols (accumulated or overwritten alike) and added afterwards
+        let mut scratch = vec![vec![[0u8; 2]; delta.len()]; parity_shards];
+        encoder.encode_single_sep(index, &delta, &mut scratch)?;
+        for (shard, change) in parity.iter_mut().zip(&scratch) {
+            xor_into(shard, &change.concat());
+        }
+        Ok(())
+    }
+    fn encode_incremental(
+        &self,
+        data_shards: usize,
+        parity_shards: usize,
+        arrivals: &mut dyn Iterator<Item = Vec<u8>>,
+    ) -> Result<Vec<Vec<u8>>> {
+        let encoder =
+            galois_16::ReedSolomon::new(data_shards, parity_shards).map_err(unsupported)?;
+        let mut sbs = ShardByShard::new(&encoder);
+
+        // Every slot must be allocated at the shard size, known from the first arrival
+        let mut shards: Vec<Vec<[u8; 2]>> = Vec::new();
+        for (index, shard) in arrivals.enumerate() {
+            if shards.is_empty() {
+                shards = vec![vec![[0u8; 2]; shard.len() / 2]; data_shards + parity_shards];
+            }
+            check_arrival(data_shards, index, shard.len(), 2 * shards[0].len())?;
+            shards[index] = to_symbols(&shard);
+            sbs.encode(&mut shards)?;
+        }
+        check_all_arrived(data_shards, sbs.cur_input_index())?;
+        Ok(shards.iter().map(|shard| shard.concat()).collect())
+    }
+}
+
+/// Reinterprets a byte shard as GF(2^16) symbols; a trailing odd byte is dropped.
+fn to_symbols(shard: &[u8]) -> Vec<[u8; 2]> {
+    shard.chunks_exact(2).map(|s| [s[0], s[1]]).collect()
+}
+
+/// `ReedSolomon::new` only fails for shard counts it cannot encode.
+fn unsupported(err: reed_solomon_erasure::Error) -> CodecError {
+    CodecError::wrap(ErrorKind::UnsupportedParams, err)
+}
+
+impl From<reed_solomon_erasure::Error> for CodecError {
+    fn from(err: reed_solomon_erasure::Error) -> Self {
+        use reed_solomon_erasure::Error::*;
+        let kind = match err {
+            TooFewShardsPresent => ErrorKind::TooFewShards,
+            TooFewShards | TooManyShards | TooFewBufferShards | TooManyBufferShards
+            | IncorrectShardSize | EmptyShard | InvalidShardFlags => ErrorKind::SizeMismatch,
+            TooFewDataShards | TooManyDataShards | TooFewParityShards | TooManyParityShards => {
+                ErrorKind::UnsupportedParams
+            }
+            InvalidIndex => ErrorKind::InvalidIndex,
+        };
+        CodecError::wrap(kind, err)
+    }
+}
+
+impl From<reed_solomon_erasure::SBSError> for CodecError {
+    fn from(err: reed_solomon_erasure::SBSError) -> Self {
+        match err {
+            reed_solomon_erasure::SBSError::RSError(err) => err.into(),
+            // More or fewer shards fed to the shard-by-shard encoder than it takes
+            _ => CodecError::wrap(ErrorKind::SizeMismatch, err),
+        }
+    }
+}
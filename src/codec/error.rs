@@ -0,0 +1,118 @@
+//! One error type for every backend, so callers can react to the cause
+//! rather than to four crates' error enums.
+
+use std::error::Error;
+use std::fmt;
+
+/// Why an adapter call failed, whichever backend it went to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    /// Fewer shards present than decoding needs.
+    TooFewShards,
+    /// Shards of the wrong or inconsistent length, or a shard list of the wrong length.
+    SizeMismatch,
+    /// Shard counts the backend cannot encode.
+    UnsupportedParams,
+    /// An operation the backend has no way to perform.
+    UnsupportedOperation,
+    /// Two different shards given for the same index.
+    DuplicateIndex,
+    /// A shard index past the last shard.
+    InvalidIndex,
+    /// Anything else, e.g. I/O while generating a payload.
+    Other,
+}
+
+impl ErrorKind {
+    pub const ALL: &'static [ErrorKind] = &[
+        ErrorKind::TooFewShards,
+        ErrorKind::SizeMismatch,
+        ErrorKind::UnsupportedParams,
+        ErrorKind::UnsupportedOperation,
+        ErrorKind::DuplicateIndex,
+        ErrorKind::InvalidIndex,
+        ErrorKind::Other,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ErrorKind::TooFewShards => "too_few_shards",
+            ErrorKind::SizeMismatch => "size_mismatch",
+            ErrorKind::UnsupportedParams => "unsupported_params",
+            ErrorKind::UnsupportedOperation => "unsupported_operation",
+            ErrorKind::DuplicateIndex => "duplicate_index",
+            ErrorKind::InvalidIndex => "invalid_index",
+            ErrorKind::Other => "other",
+        }
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Error returned by the adapters: a common [`ErrorKind`] plus the
+/// backend's own error, when there is one, as the source.
+#[derive(Debug)]
+pub struct CodecError {
+    kind: ErrorKind,
+    message: String,
+    source: Option<Box<dyn Error + Send + Sync>>,
+}
+
+impl CodecError {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            source: None,
+        }
+    }
+
+    /// Wraps a backend or library error under `kind`, keeping its message.
+    pub fn wrap(kind: ErrorKind, source: impl Error + Send + Sync + 'static) -> Self {
+        Self {
+            kind,
+            message: source.to_string(),
+            source: Some(Box::new(source)),
+        }
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for CodecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for CodecError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source
+            .as_deref()
+            .map(|source| source as &(dyn Error + 'static))
+    }
+}
+
+impl From<String> for CodecError {
+    fn from(message: String) -> Self {
+        Self::new(ErrorKind::Other, message)
+    }
+}
+
+impl From<&str> for CodecError {
+    fn from(message: &str) -> Self {
+        Self::new(ErrorKind::Other, message)
+    }
+}
+
+impl From<std::io::Error> for CodecError {
+    fn from(err: std::io::Error) -> Self {
+        Self::wrap(ErrorKind::Other, err)
+    }
+}
@@ -0,0 +1,249 @@
+//! Adapter shared by reed-solomon-16 and reed-solomon-simd.
+//!
+//! reed-solomon-simd grew out of reed-solomon-16 and kept its API and error
+//! type, so both adapters are generated from one body, parameterised on the
+//! crate, the shard alignment it requires and the map its `decode` returns.
+
+/// Defines `$codec`, an [`ErasureCodec`](super::ErasureCodec) over `$krate`.
+macro_rules! leopard_codec {
+    (
+        $(#[$attr:meta])*
+        $codec:ident, $krate:ident, $name:literal, alignment = $alignment:literal,
+        map = $map:ident
+    ) => {
+        use super::{
+            aligned_shard_size, check_all_arrived, check_arrival, check_counts, check_indices,
+            check_shards, restore_len, shard_delta, split_shards, xor_into, Capabilities,
+            CodecError, ErasureCodec, ErrorKind, Result,
+        };
+        use std::collections::$map;
+
+        $(#[$attr])*
+        pub struct $codec;
+
+        impl ErasureCodec for $codec {
+            fn name(&self) -> &'static str {
+                $name
+            }
+
+            fn capabilities(&self) -> Capabilities {
+                Capabilities {
+                    max_data_shards: 65535,
+                    max_parity_shards: 65535,
+                    max_total_shards: 65536,
+                    shard_alignment: $alignment,
+                    min_shard_size: $alignment,
+                }
+            }
+
+            fn supports(&self, data_shards: usize, parity_shards: usize) -> bool {
+                // The smaller side is rounded up to a power of two before the 2^16 check
+                $krate::ReedSolomonEncoder::supports(data_shards, parity_shards)
+            }
+
+            fn encode(
+                &self,
+                data_shards: usize,
+                parity_shards: usize,
+                data: &[u8],
+            ) -> Result<Vec<Vec<u8>>> {
+                check_counts(data_shards, parity_shards)?;
+                // Shards are rounded up to 64 bytes as in the benches, whatever the
+                // crate's own alignment, so every backend codes the same shard sizes
+                let shard_size = aligned_shard_size(data.len(), data_shards);
+
+                let mut shards = split_shards(data, data_shards, shard_size);
+                let recovery = $krate::encode(data_shards, parity_shards, &shards)?;
+                shards.extend(recovery);
+                Ok(shards)
+            }
+
+            fn decode(
+                &self,
+                data_shards: usize,
+                parity_shards: usize,
+                shards: &[Option<Vec<u8>>],
+                data_len: usize,
+            ) -> Result<Vec<u8>> {
+                check_shards(&self.capabilities(), data_shards, parity_shards, shards)?;
+                let mut restored = decode_missing(data_shards, parity_shards, shards)?;
+                let mut data = Vec::new();
+                for (index, shard) in shards.iter().take(data_shards).enumerate() {
+                    match shard {
+                        Some(shard) => data.extend_from_slice(shard),
+                        None => data.extend(restored.remove(&index).ok_or(UNRESTORED)?),
+                    }
+                }
+                restore_len(data, data_len)
+            }
+
+            fn reconstruct(
+                &self,
+                data_shards: usize,
+                parity_shards: usize,
+                shards: &[Option<Vec<u8>>],
+            ) -> Result<Vec<Vec<u8>>> {
+                check_shards(&self.capabilities(), data_shards, parity_shards, shards)?;
+                let mut originals = restore_originals(data_shards, parity_shards, shards)?;
+
+                // The crate only restores originals; missing recovery shards are re-encoded
+                let recovery = $krate::encode(data_shards, parity_shards, &originals)?;
+                originals.extend(recovery);
+                Ok(originals)
+            }
+
+            fn repair(
+                &self,
+                data_shards: usize,
+                parity_shards: usize,
+                shards: &[Option<Vec<u8>>],
+                missing: &[usize],
+            ) -> Result<Vec<Vec<u8>>> {
+                check_indices(missing, data_shards + parity_shards)?;
+                check_shards(&self.capabilities(), data_shards, parity_shards, shards)?;
+                let originals = restore_originals(data_shards, parity_shards, shards)?;
+
+                // Re-encoding is only needed when parity was asked for
+                let recovery = if missing.iter().any(|&index| index >= data_shards) {
+                    $krate::encode(data_shards, parity_shards, &originals)?
+                } else {
+                    Vec::new()
+                };
+                Ok(missing
+                    .iter()
+                    .map(|&index| match index.checked_sub(data_shards) {
+                        Some(parity) => recovery[parity].clone(),
+                        None => originals[index].clone(),
+                    })
+                    .collect())
+            }
+
+            fn update_parity(
+                &self,
+                data_shards: usize,
+                parity_shards: usize,
+                index: usize,
+                old: &[u8],
+                new: &[u8],
+                parity: &mut [Vec<u8>],
+            ) -> Result<()> {
+                let delta = shard_delta(data_shards, parity_shards, index, old, new, parity)?;
+
+                // No single-shard entry point: by linearity, encode the delta with every
+                // other original zeroed and add the resulting recovery shards
+                let mut originals = vec![vec![0u8; delta.len()]; data_shards];
+                originals[index] = delta;
+                let change = $krate::encode(data_shards, parity_shards, &originals)?;
+                for (shard, change) in parity.iter_mut().zip(&change) {
+                    xor_into(shard, change);
+                }
+                Ok(())
+            }
+
+            fn encode_incremental(
+                &self,
+                data_shards: usize,
+                parity_shards: usize,
+                arrivals: &mut dyn Iterator<Item = Vec<u8>>,
+            ) -> Result<Vec<Vec<u8>>> {
+                // The encoder needs the shard size, known from the first arrival
+                let mut encoder: Option<$krate::ReedSolomonEncoder> = None;
+                let mut shards = Vec::with_capacity(data_shards + parity_shards);
+                for (index, shard) in arrivals.enumerate() {
+                    let encoder = match &mut encoder {
+                        Some(encoder) => encoder,
+                        None => encoder.insert($krate::ReedSolomonEncoder::new(
+                            data_shards,
+                            parity_shards,
+                            shard.len(),
+                        )?),
+                    };
+                    check_arrival(
+                        data_shards,
+                        index,
+                        shard.len(),
+                        shards.first().map_or(shard.len(), Vec::len),
+                    )?;
+                    encoder.add_original_shard(&shard)?;
+                    shards.push(shard);
+                }
+                check_all_arrived(data_shards, shards.len())?;
+
+                let mut encoder = encoder.ok_or("no data shards arrived")?;
+                let result = encoder.encode()?;
+                shards.extend(result.recovery_iter().map(<[u8]>::to_vec));
+                Ok(shards)
+            }
+        }
+
+        /// The crate left out an original it was asked to restore.
+        const UNRESTORED: &str = "decoder did not restore every missing original";
+
+        /// Every original shard, decoding the missing ones only if there are any.
+        fn restore_originals(
+            data_shards: usize,
+            parity_shards: usize,
+            shards: &[Option<Vec<u8>>],
+        ) -> Result<Vec<Vec<u8>>> {
+            let mut restored = if shards.iter().take(data_shards).all(Option::is_some) {
+                $map::new()
+            } else {
+                decode_missing(data_shards, parity_shards, shards)?
+            };
+            shards
+                .iter()
+                .take(data_shards)
+                .enumerate()
+                .map(|(index, shard)| match shard {
+                    Some(shard) => Ok(shard.clone()),
+                    None => Ok(restored.remove(&index).ok_or(UNRESTORED)?),
+                })
+                .collect()
+        }
+
+        /// Restores the missing originals of `shards`, keyed by shard index.
+        fn decode_missing(
+            data_shards: usize,
+            parity_shards: usize,
+            shards: &[Option<Vec<u8>>],
+        ) -> Result<$map<usize, Vec<u8>>> {
+            // Split by index rather than by slicing, which would panic on a short list
+            let present = shards
+                .iter()
+                .enumerate()
+                .filter_map(|(index, shard)| shard.as_ref().map(|shard| (index, shard)));
+            let original = present.clone().filter(|&(index, _)| index < data_shards);
+            let recovery =
+                present.filter_map(|(index, shard)| Some((index.checked_sub(data_shards)?, shard)));
+
+            Ok($krate::decode(
+                data_shards,
+                parity_shards,
+                original,
+                recovery,
+            )?)
+        }
+
+        impl From<$krate::Error> for CodecError {
+            fn from(err: $krate::Error) -> Self {
+                use $krate::Error::*;
+                let kind = match err {
+                    NotEnoughShards { .. } => ErrorKind::TooFewShards,
+                    DifferentShardSize { .. } | InvalidShardSize { .. } => ErrorKind::SizeMismatch,
+                    TooFewOriginalShards { .. }
+                    | TooManyOriginalShards { .. }
+                    | UnsupportedShardCount { .. } => ErrorKind::UnsupportedParams,
+                    DuplicateOriginalShardIndex { .. } | DuplicateRecoveryShardIndex { .. } => {
+                        ErrorKind::DuplicateIndex
+                    }
+                    InvalidOriginalShardIndex { .. } | InvalidRecoveryShardIndex { .. } => {
+                        ErrorKind::InvalidIndex
+                    }
+                };
+                CodecError::wrap(kind, err)
+            }
+        }
+    };
+}
+
+pub(super) use leopard_codec;
@@ -0,0 +1,679 @@
+//! Uniform adapters over the benchmarked Reed-Solomon crates.
+//!
+//! Each backend lives in its own module and is only compiled when its cargo
+//! feature is enabled, so a binary can embed exactly the codecs it ships.
+
+#[cfg(feature = "erasure")]
+mod erasure;
+mod error;
+#[cfg(any(feature = "rs16", feature = "simd"))]
+mod leopard;
+#[cfg(feature = "novelpoly")]
+mod novelpoly;
+#[cfg(feature = "rs16")]
+mod rs16;
+mod session;
+#[cfg(feature = "simd")]
+mod simd;
+
+#[cfg(feature = "erasure")]
+pub use erasure::{Erasure, Erasure16};
+pub use error::{CodecError, ErrorKind};
+#[cfg(feature = "novelpoly")]
+pub use novelpoly::Novelpoly;
+#[cfg(feature = "rs16")]
+pub use rs16::Rs16;
+pub use session::DecoderSession;
+#[cfg(feature = "simd")]
+pub use simd::Simd;
+
+/// Error returned by the adapters; wraps the backend's own error type.
+pub type Error = CodecError;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Parameter limits a backend advertises, so sweeps can skip what it cannot run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+    pub max_data_shards: usize,
+    pub max_parity_shards: usize,
+    pub max_total_shards: usize,
+    /// The backend's own shard sizes must be a multiple of this many bytes.
+    pub shard_alignment: usize,
+    pub min_shard_size: usize,
+}
+
+impl Capabilities {
+    /// Whether the shard counts are within the advertised maxima.
+    pub fn admits(&self, data_shards: usize, parity_shards: usize) -> bool {
+        data_shards >= 1
+            && parity_shards >= 1
+            && data_shards <= self.max_data_shards
+            && parity_shards <= self.max_parity_shards
+            && data_shards + parity_shards <= self.max_total_shards
+    }
+
+    /// Fewest zero bytes the backend's own shard rules add to `data_len` bytes
+    /// split over `data_shards`, without the benches' 64-byte rounding.
+    pub fn min_padding(&self, data_len: usize, data_shards: usize) -> usize {
+        let shard_size = data_len
+            .div_ceil(data_shards)
+            .next_multiple_of(self.shard_alignment)
+            .max(self.min_shard_size);
+        data_shards * shard_size - data_len
+    }
+}
+
+/// A Reed-Solomon backend behind a common interface.
+///
+/// Shards are indexed `0..data_shards` for data and
+/// `data_shards..data_shards + parity_shards` for parity.
+pub trait ErasureCodec {
+    /// Short name matching the bench group suffix (e.g. `rs16` for `encode_rs16`).
+    fn name(&self) -> &'static str;
+
+    /// Name shown in reports, tagged with build options that change the backend's code.
+    fn label(&self) -> &'static str {
+        self.name()
+    }
+
+    fn capabilities(&self) -> Capabilities;
+
+    /// Whether `data_shards`/`parity_shards` can be encoded at all.
+    ///
+    /// Backends whose limits are not a simple box override this with their own check.
+    fn supports(&self, data_shards: usize, parity_shards: usize) -> bool {
+        self.capabilities().admits(data_shards, parity_shards)
+    }
+
+    /// Encodes `data` into `data_shards + parity_shards` equal-length shards.
+    fn encode(&self, data_shards: usize, parity_shards: usize, data: &[u8])
+        -> Result<Vec<Vec<u8>>>;
+
+    /// Recovers the payload from `shards`, where missing shards are `None`.
+    ///
+    /// `data_len` is the length of the payload passed to [`encode`](Self::encode);
+    /// the result is exactly that long, with the shard padding stripped.
+    fn decode(
+        &self,
+        data_shards: usize,
+        parity_shards: usize,
+        shards: &[Option<Vec<u8>>],
+        data_len: usize,
+    ) -> Result<Vec<u8>>;
+
+    /// Recovers every shard, parity included, from `shards`, where missing shards are `None`.
+    ///
+    /// [`decode`](Self::decode) stops once the payload is back; this also
+    /// rebuilds parity, as a node must before handing its lost shard out again.
+    fn reconstruct(
+        &self,
+        data_shards: usize,
+        parity_shards: usize,
+        shards: &[Option<Vec<u8>>],
+    ) -> Result<Vec<Vec<u8>>>;
+
+    /// Regenerates the shards at `missing`, data or parity, in that order.
+    ///
+    /// `shards` may lack more than `missing` as long as enough remain to decode.
+    /// The default rebuilds everything; backends override it to skip work the
+    /// requested shards do not need.
+    fn repair(
+        &self,
+        data_shards: usize,
+        parity_shards: usize,
+        shards: &[Option<Vec<u8>>],
+        missing: &[usize],
+    ) -> Result<Vec<Vec<u8>>> {
+        check_indices(missing, data_shards + parity_shards)?;
+        let restored = self.reconstruct(data_shards, parity_shards, shards)?;
+        Ok(missing
+            .iter()
+            .map(|&index| restored[index].clone())
+            .collect())
+    }
+
+    /// Updates `parity` in place after data shard `index` changed from `old` to `new`.
+    ///
+    /// The codes are linear, so the parity moves by the encoding of `old ^ new`
+    /// alone; backends that can encode a single shard do only that much work.
+    fn update_parity(
+        &self,
+        data_shards: usize,
+        parity_shards: usize,
+        index: usize,
+        old: &[u8],
+        new: &[u8],
+        parity: &mut [Vec<u8>],
+    ) -> Result<()>;
+
+    /// Encodes data shards pulled from `arrivals` in index order, as they come in.
+    ///
+    /// Backends that can absorb one shard at a time do so on arrival, so only
+    /// the last shard and the finishing step remain once the final one is in.
+    /// For arrivals of a multiple of 64 bytes, returns what [`encode`](Self::encode)
+    /// returns for their concatenation.
+    fn encode_incremental(
+        &self,
+        data_shards: usize,
+        parity_shards: usize,
+        arrivals: &mut dyn Iterator<Item = Vec<u8>>,
+    ) -> Result<Vec<Vec<u8>>>;
+}
+
+/// All backends compiled into this build.
+pub fn backends() -> Vec<Box<dyn ErasureCodec>> {
+    vec![
+        #[cfg(feature = "erasure")]
+        Box::new(Erasure),
+        #[cfg(feature = "erasure")]
+        Box::new(Erasure16),
+        #[cfg(feature = "novelpoly")]
+        Box::new(Novelpoly),
+        #[cfg(feature = "rs16")]
+        Box::new(Rs16),
+        #[cfg(feature = "simd")]
+        Box::new(Simd),
+    ]
+}
+
+/// Looks up a compiled-in backend by name.
+pub fn backend(name: &str) -> Option<Box<dyn ErasureCodec>> {
+    backends().into_iter().find(|codec| codec.name() == name)
+}
+
+/// Rejects codes without a data or a parity shard before anything divides by them.
+#[cfg(any(
+    feature = "erasure",
+    feature = "novelpoly",
+    feature = "rs16",
+    feature = "simd"
+))]
+pub(crate) fn check_counts(data_shards: usize, parity_shards: usize) -> Result<()> {
+    if data_shards == 0 || parity_shards == 0 {
+        return Err(CodecError::new(
+            ErrorKind::UnsupportedParams,
+            "at least one data and one parity shard are needed",
+        ));
+    }
+    Ok(())
+}
+
+/// Shard size for `data_len` bytes over `data_shards`, rounded up to 64 bytes.
+pub(crate) fn aligned_shard_size(data_len: usize, data_shards: usize) -> usize {
+    data_len.div_ceil(data_shards).div_ceil(64).max(1) * 64
+}
+
+/// Strips shard padding from a recovered payload of original length `data_len`.
+#[cfg(any(
+    feature = "erasure",
+    feature = "novelpoly",
+    feature = "rs16",
+    feature = "simd"
+))]
+pub(crate) fn restore_len(mut data: Vec<u8>, data_len: usize) -> Result<Vec<u8>> {
+    if data.len() < data_len {
+        return Err(CodecError::new(
+            ErrorKind::SizeMismatch,
+            format!(
+                "recovered {} bytes, fewer than the original {}",
+                data.len(),
+                data_len
+            ),
+        ));
+    }
+    data.truncate(data_len);
+    Ok(data)
+}
+
+/// Rejects shard indices outside `0..total_shards`.
+pub(crate) fn check_indices(indices: &[usize], total_shards: usize) -> Result<()> {
+    match indices.iter().find(|&&index| index >= total_shards) {
+        Some(index) => Err(CodecError::new(
+            ErrorKind::InvalidIndex,
+            format!(
+                "shard index {} out of range for {} shards",
+                index, total_shards
+            ),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Rejects shards that cannot be a codeword of `data_shards + parity_shards`:
+/// a list of another length, fewer than `data_shards` present, or present
+/// shards that are empty, differ in length or break the backend's alignment.
+/// Returns the shard length.
+#[cfg(any(
+    feature = "erasure",
+    feature = "novelpoly",
+    feature = "rs16",
+    feature = "simd"
+))]
+pub(crate) fn check_shards(
+    caps: &Capabilities,
+    data_shards: usize,
+    parity_shards: usize,
+    shards: &[Option<Vec<u8>>],
+) -> Result<usize> {
+    let mismatch = |message: String| Err(CodecError::new(ErrorKind::SizeMismatch, message));
+    check_counts(data_shards, parity_shards)?;
+    if Some(shards.len()) != data_shards.checked_add(parity_shards) {
+        return mismatch(format!(
+            "expected {} + {} shards, got {}",
+            data_shards,
+            parity_shards,
+            shards.len()
+        ));
+    }
+    let mut present = shards.iter().flatten();
+    let shard_len = present.next().map_or(0, Vec::len);
+    let count = shards.iter().flatten().count();
+    if count < data_shards {
+        return Err(CodecError::new(
+            ErrorKind::TooFewShards,
+            format!("only {} shards present, {} are needed", count, data_shards),
+        ));
+    }
+    if shard_len == 0 {
+        return mismatch("present shards are empty".to_string());
+    }
+    if present.any(|shard| shard.len() != shard_len) {
+        return mismatch("present shards differ in length".to_string());
+    }
+    if !shard_len.is_multiple_of(caps.shard_alignment) {
+        return mismatch(format!(
+            "{}-byte shards are not a multiple of {} bytes",
+            shard_len, caps.shard_alignment
+        ));
+    }
+    Ok(shard_len)
+}
+
+/// `old ^ new`, after checking the arguments of [`ErasureCodec::update_parity`].
+#[cfg(any(feature = "erasure", feature = "rs16", feature = "simd"))]
+pub(crate) fn shard_delta(
+    data_shards: usize,
+    parity_shards: usize,
+    index: usize,
+    old: &[u8],
+    new: &[u8],
+    parity: &[Vec<u8>],
+) -> Result<Vec<u8>> {
+    if index >= data_shards {
+        return Err(CodecError::new(
+            ErrorKind::InvalidIndex,
+            format!(
+                "data shard index {} out of range for {}",
+                index, data_shards
+            ),
+        ));
+    }
+    if parity.len() != parity_shards {
+        return Err(CodecError::new(
+            ErrorKind::SizeMismatch,
+            format!(
+                "expected {} parity shards, got {}",
+                parity_shards,
+                parity.len()
+            ),
+        ));
+    }
+    if new.len() != old.len() || parity.iter().any(|shard| shard.len() != old.len()) {
+        return Err(CodecError::new(
+            ErrorKind::SizeMismatch,
+            "old, new and parity shards differ in length",
+        ));
+    }
+    Ok(old.iter().zip(new).map(|(a, b)| a ^ b).collect())
+}
+
+/// XORs `src` into `dst`; addition in GF(2^n).
+#[cfg(any(feature = "erasure", feature = "rs16", feature = "simd"))]
+pub(crate) fn xor_into(dst: &mut [u8], src: &[u8]) {
+    for (d, s) in dst.iter_mut().zip(src) {
+        *d ^= s;
+    }
+}
+
+/// Rejects an arrival beyond `data_shards` or one whose length differs from the first.
+#[cfg(any(
+    feature = "erasure",
+    feature = "novelpoly",
+    feature = "rs16",
+    feature = "simd"
+))]
+pub(crate) fn check_arrival(
+    data_shards: usize,
+    index: usize,
+    len: usize,
+    first: usize,
+) -> Result<()> {
+    if index >= data_shards {
+        return Err(CodecError::new(
+            ErrorKind::SizeMismatch,
+            format!("more than {} data shards arrived", data_shards),
+        ));
+    }
+    if len != first {
+        return Err(CodecError::new(
+            ErrorKind::SizeMismatch,
+            format!("shard {} is {} bytes, shard 0 is {}", index, len, first),
+        ));
+    }
+    Ok(())
+}
+
+/// Rejects an arrival stream that ended after `arrived` of `data_shards` shards.
+#[cfg(any(
+    feature = "erasure",
+    feature = "novelpoly",
+    feature = "rs16",
+    feature = "simd"
+))]
+pub(crate) fn check_all_arrived(data_shards: usize, arrived: usize) -> Result<()> {
+    if arrived < data_shards {
+        return Err(CodecError::new(
+            ErrorKind::TooFewShards,
+            format!("only {} of {} data shards arrived", arrived, data_shards),
+        ));
+    }
+    Ok(())
+}
+
+/// Splits `data` into `count` shards of `shard_size` bytes, zero-padding the tail.
+pub(crate) fn split_shards(data: &[u8], count: usize, shard_size: usize) -> Vec<Vec<u8>> {
+    (0..count)
+        .map(|i| {
+            let start = std::cmp::min(i * shard_size, data.len());
+            let end = std::cmp::min(start + shard_size, data.len());
+            let mut shard = data[start..end].to_vec();
+            shard.resize(shard_size, 0);
+            shard
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{unaligned_configs, BenchConfig, LARGE_F_VALUES};
+
+    fn payload(size: usize) -> Vec<u8> {
+        (0..size).map(|i| (i % 251) as u8).collect()
+    }
+
+    #[test]
+    fn round_trip_all_backends() {
+        for codec in backends() {
+            for config in [BenchConfig::new(1, 4096), BenchConfig::new(5, 65536)] {
+                let data = payload(config.data_size);
+                let shards = codec
+                    .encode(config.data_shards(), config.coding_shards(), &data)
+                    .unwrap();
+                assert_eq!(shards.len(), config.total_shards(), "{}", codec.name());
+
+                // Drop the first 2F shards, the most any config can lose
+                let received: Vec<Option<Vec<u8>>> = shards
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, shard)| (i >= config.coding_shards()).then_some(shard))
+                    .collect();
+                let decoded = codec
+                    .decode(
+                        config.data_shards(),
+                        config.coding_shards(),
+                        &received,
+                        data.len(),
+                    )
+                    .unwrap();
+                assert_eq!(decoded, data, "{}", codec.name());
+            }
+        }
+    }
+
+    #[test]
+    fn unaligned_sizes_restore_exact_length() {
+        for codec in backends() {
+            for config in unaligned_configs() {
+                let data = payload(config.data_size);
+                let (k, m) = (config.data_shards(), config.coding_shards());
+                let shards = codec.encode(k, m, &data).unwrap();
+
+                // Lose F shards from the middle, mixing data and parity
+                let received: Vec<Option<Vec<u8>>> = shards
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, shard)| (i < k / 2 || i >= k / 2 + config.f).then_some(shard))
+                    .collect();
+                let decoded = codec.decode(k, m, &received, data.len()).unwrap();
+                assert_eq!(decoded.len(), data.len(), "{} {:?}", codec.name(), config);
+                assert!(decoded == data, "{} {:?}", codec.name(), config);
+            }
+        }
+    }
+
+    #[test]
+    fn reconstruct_restores_data_and_parity() {
+        for codec in backends() {
+            for config in [BenchConfig::new(1, 4096), BenchConfig::new(5, 65536 + 3)] {
+                let data = payload(config.data_size);
+                let (k, m) = (config.data_shards(), config.coding_shards());
+                let shards = codec.encode(k, m, &data).unwrap();
+
+                // Lose 2F shards from the end of the data through the parity
+                let received: Vec<Option<Vec<u8>>> = shards
+                    .iter()
+                    .enumerate()
+                    .map(|(i, shard)| (i < k - 1 || i >= k - 1 + m).then(|| shard.clone()))
+                    .collect();
+                let restored = codec.reconstruct(k, m, &received).unwrap();
+                assert!(restored == shards, "{} {:?}", codec.name(), config);
+            }
+        }
+    }
+
+    #[test]
+    fn repair_regenerates_requested_shards() {
+        let config = BenchConfig::new(3, 4096);
+        let (k, m) = (config.data_shards(), config.coding_shards());
+        let data = payload(config.data_size);
+        for codec in backends() {
+            let shards = codec.encode(k, m, &data).unwrap();
+            for missing in [
+                vec![0],
+                vec![k + m - 1],
+                vec![k + 1, 1, k + 4],
+                (k..k + m).collect(),
+            ] {
+                // Also withhold a shard that is not asked for, while m shards remain lost at most
+                let absent = (missing.len() < m)
+                    .then(|| (0..k + m).find(|index| !missing.contains(index)))
+                    .flatten();
+                let received: Vec<Option<Vec<u8>>> = shards
+                    .iter()
+                    .enumerate()
+                    .map(|(i, shard)| {
+                        (!missing.contains(&i) && Some(i) != absent).then(|| shard.clone())
+                    })
+                    .collect();
+                let repaired = codec.repair(k, m, &received, &missing).unwrap();
+                let expected: Vec<&Vec<u8>> = missing.iter().map(|&i| &shards[i]).collect();
+                assert!(
+                    repaired.iter().eq(expected),
+                    "{} {:?}",
+                    codec.name(),
+                    missing
+                );
+            }
+            let received: Vec<Option<Vec<u8>>> = shards.into_iter().map(Some).collect();
+            assert!(
+                codec.repair(k, m, &received, &[k + m]).is_err(),
+                "{}",
+                codec.name()
+            );
+        }
+    }
+
+    #[test]
+    fn update_parity_matches_reencoding() {
+        let config = BenchConfig::new(3, 4096);
+        let (k, m) = (config.data_shards(), config.coding_shards());
+        for codec in backends() {
+            let shards = codec.encode(k, m, &payload(config.data_size)).unwrap();
+            for index in [0, k - 1] {
+                let new: Vec<u8> = shards[index]
+                    .iter()
+                    .map(|b| b.wrapping_mul(7) ^ 0x5a)
+                    .collect();
+                let mut parity = shards[k..].to_vec();
+                let result = codec.update_parity(k, m, index, &shards[index], &new, &mut parity);
+                if codec.name() == "novelpoly" {
+                    assert!(result.is_err());
+                    continue;
+                }
+                result.unwrap();
+
+                let mut data_shards = shards[..k].to_vec();
+                data_shards[index] = new;
+                let expected = codec.encode(k, m, &data_shards.concat()).unwrap();
+                assert!(parity == expected[k..], "{} shard {}", codec.name(), index);
+            }
+        }
+    }
+
+    #[test]
+    fn incremental_encoding_matches_encode() {
+        let config = BenchConfig::new(5, 65536);
+        let (k, m) = (config.data_shards(), config.coding_shards());
+        let data = payload(config.data_size);
+        let originals = split_shards(&data, k, aligned_shard_size(data.len(), k));
+        for codec in backends() {
+            let shards = codec
+                .encode_incremental(k, m, &mut originals.iter().cloned())
+                .unwrap();
+            assert!(
+                shards == codec.encode(k, m, &originals.concat()).unwrap(),
+                "{}",
+                codec.name()
+            );
+
+            let mut too_few = originals[1..].iter().cloned();
+            assert!(
+                codec.encode_incremental(k, m, &mut too_few).is_err(),
+                "{}",
+                codec.name()
+            );
+            let mut too_many = originals.iter().chain(&originals[..1]).cloned();
+            assert!(
+                codec.encode_incremental(k, m, &mut too_many).is_err(),
+                "{}",
+                codec.name()
+            );
+        }
+    }
+
+    #[test]
+    fn decode_rejects_length_beyond_capacity() {
+        for codec in backends() {
+            let data = payload(100);
+            let shards: Vec<Option<Vec<u8>>> = codec
+                .encode(2, 4, &data)
+                .unwrap()
+                .into_iter()
+                .map(Some)
+                .collect();
+            let capacity = 2 * shards[0].as_ref().unwrap().len();
+            assert!(
+                codec.decode(2, 4, &shards, capacity + 1).is_err(),
+                "{}",
+                codec.name()
+            );
+        }
+    }
+
+    #[test]
+    fn bench_configs_are_supported() {
+        for codec in backends() {
+            for &f in crate::config::F_VALUES {
+                assert!(codec.supports(f, 2 * f), "{} F{}", codec.name(), f);
+            }
+        }
+    }
+
+    #[test]
+    fn min_padding_fits_within_bench_padding() {
+        for codec in backends() {
+            let caps = codec.capabilities();
+            for config in crate::config::small_configs() {
+                let padding = caps.min_padding(config.data_size, config.data_shards());
+                let shard_size = (config.data_size + padding) / config.data_shards();
+                assert!(padding <= config.padding(), "{} {:?}", codec.name(), config);
+                assert_eq!(
+                    shard_size * config.data_shards(),
+                    config.data_size + padding,
+                    "{} {:?}",
+                    codec.name(),
+                    config
+                );
+                assert!(
+                    shard_size.is_multiple_of(caps.shard_alignment),
+                    "{} {:?}",
+                    codec.name(),
+                    config
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn large_tier_fits_gf16_backends_only() {
+        for codec in backends() {
+            let gf16 = codec.capabilities().max_total_shards > 256;
+            for &f in LARGE_F_VALUES {
+                assert_eq!(codec.supports(f, 2 * f), gf16, "{} F{}", codec.name(), f);
+            }
+        }
+    }
+
+    #[test]
+    fn large_committee_round_trip() {
+        let config = BenchConfig::new(LARGE_F_VALUES[0], 4096);
+        let (k, m) = (config.data_shards(), config.coding_shards());
+        for codec in backends().into_iter().filter(|codec| codec.supports(k, m)) {
+            let data = payload(config.data_size);
+            let received: Vec<Option<Vec<u8>>> = codec
+                .encode(k, m, &data)
+                .unwrap()
+                .into_iter()
+                .enumerate()
+                .map(|(i, shard)| (i >= config.f).then_some(shard))
+                .collect();
+            assert_eq!(received.len(), config.total_shards(), "{}", codec.name());
+            let decoded = codec.decode(k, m, &received, data.len()).unwrap();
+            assert!(decoded == data, "{}", codec.name());
+        }
+    }
+
+    #[test]
+    fn unsupported_params_are_errors_not_panics() {
+        for codec in backends() {
+            let too_many = codec.capabilities().max_total_shards;
+            assert!(!codec.supports(1, too_many), "{}", codec.name());
+            assert!(
+                codec.encode(1, too_many, &[1, 2, 3]).is_err(),
+                "{}",
+                codec.name()
+            );
+            assert!(!codec.supports(0, 2), "{}", codec.name());
+        }
+    }
+
+    #[test]
+    fn backend_lookup_by_name() {
+        for codec in backends() {
+            assert_eq!(backend(codec.name()).unwrap().name(), codec.name());
+        }
+        assert!(backend("no-such-backend").is_none());
+    }
+}
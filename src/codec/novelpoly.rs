@@ -0,0 +1,135 @@
+//! reed-solomon-novelpoly adapter.
+
+use super::{
+    check_all_arrived, check_arrival, check_shards, restore_len, Capabilities, CodecError,
+    ErasureCodec, ErrorKind, Result,
+};
+use reed_solomon_novelpoly::{CodeParams, WrappedShard};
+
+/// `reed_solomon_novelpoly`, which rounds `n` up and `k` down to powers of two.
+pub struct Novelpoly;
+
+impl ErasureCodec for Novelpoly {
+    fn name(&self) -> &'static str {
+        "novelpoly"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        // n is rounded up to a power of two, which must not exceed 2^16
+        Capabilities {
+            max_data_shards: 65535,
+            max_parity_shards: 65535,
+            max_total_shards: 65536,
+            shard_alignment: 2,
+            min_shard_size: 2,
+        }
+    }
+
+    fn encode(
+        &self,
+        data_shards: usize,
+        parity_shards: usize,
+        data: &[u8],
+    ) -> Result<Vec<Vec<u8>>> {
+        // novelpoly uses n (total) and k (data) parameters
+        let params = CodeParams::derive_parameters(data_shards + parity_shards, data_shards)?;
+        let shards: Vec<WrappedShard> = params.make_encoder().encode(data)?;
+        Ok(shards.into_iter().map(WrappedShard::into_inner).collect())
+    }
+
+    fn decode(
+        &self,
+        data_shards: usize,
+        parity_shards: usize,
+        shards: &[Option<Vec<u8>>],
+        data_len: usize,
+    ) -> Result<Vec<u8>> {
+        check_shards(&self.capabilities(), data_shards, parity_shards, shards)?;
+        let params = CodeParams::derive_parameters(data_shards + parity_shards, data_shards)?;
+        let shards: Vec<Option<WrappedShard>> = shards
+            .iter()
+            .map(|shard| shard.clone().map(WrappedShard::new))
+            .collect();
+        restore_len(params.make_encoder().reconstruct(shards)?, data_len)
+    }
+
+    fn reconstruct(
+        &self,
+        data_shards: usize,
+        parity_shards: usize,
+        shards: &[Option<Vec<u8>>],
+    ) -> Result<Vec<Vec<u8>>> {
+        check_shards(&self.capabilities(), data_shards, parity_shards, shards)?;
+        let params = CodeParams::derive_parameters(data_shards + parity_shards, data_shards)?;
+        let encoder = params.make_encoder();
+        let shards: Vec<Option<WrappedShard>> = shards
+            .iter()
+            .map(|shard| shard.clone().map(WrappedShard::new))
+            .collect();
+
+        // novelpoly only recovers the payload; every shard comes from re-encoding it
+        let data = encoder.reconstruct(shards)?;
+        let shards: Vec<WrappedShard> = encoder.encode(&data)?;
+        Ok(shards.into_iter().map(WrappedShard::into_inner).collect())
+    }
+
+    fn update_parity(
+        &self,
+        _data_shards: usize,
+        _parity_shards: usize,
+        _index: usize,
+        _old: &[u8],
+        _new: &[u8],
+        _parity: &mut [Vec<u8>],
+    ) -> Result<()> {
+        // The crate encodes payloads only: its shards interleave 2-byte symbols of
+        // the whole payload, and it has no entry point for encoding given shards
+        Err(CodecError::new(
+            ErrorKind::UnsupportedOperation,
+            "novelpoly cannot encode a single shard; re-encode the payload instead",
+        ))
+    }
+
+    fn encode_incremental(
+        &self,
+        data_shards: usize,
+        parity_shards: usize,
+        arrivals: &mut dyn Iterator<Item = Vec<u8>>,
+    ) -> Result<Vec<Vec<u8>>> {
+        // Nothing can be encoded before the whole payload is in, so arrivals are
+        // only gathered and all of the encoding falls after the last one
+        let mut data = Vec::new();
+        let mut first = None;
+        let mut arrived = 0;
+        for (index, shard) in arrivals.enumerate() {
+            check_arrival(
+                data_shards,
+                index,
+                shard.len(),
+                *first.get_or_insert(shard.len()),
+            )?;
+            data.extend_from_slice(&shard);
+            arrived += 1;
+        }
+        check_all_arrived(data_shards, arrived)?;
+        self.encode(data_shards, parity_shards, &data)
+    }
+}
+
+impl From<reed_solomon_novelpoly::Error> for CodecError {
+    fn from(err: reed_solomon_novelpoly::Error) -> Self {
+        use reed_solomon_novelpoly::Error::*;
+        let kind = match err {
+            NeedMoreShards { .. } => ErrorKind::TooFewShards,
+            InconsistentShardLengths { .. } | EmptyShard | PayloadSizeIsZero => {
+                ErrorKind::SizeMismatch
+            }
+            WantedShardCountTooHigh(_)
+            | WantedShardCountTooLow(_)
+            | WantedPayloadShardCountTooLow(_)
+            | ParamterMustBePowerOf2 { .. } => ErrorKind::UnsupportedParams,
+            _ => ErrorKind::Other,
+        };
+        CodecError::wrap(kind, err)
+    }
+}
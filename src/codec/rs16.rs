@@ -0,0 +1,8 @@
+//! reed-solomon-16 adapter.
+
+super::leopard::leopard_codec! {
+    /// `reed_solomon_16`, an O(n log n) codec over GF(2^16).
+    Rs16, reed_solomon_16, "rs16",
+    alignment = 64,
+    map = HashMap
+}
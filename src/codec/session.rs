@@ -0,0 +1,132 @@
+//! Decoding that starts as soon as enough shards have arrived.
+
+use super::{CodecError, ErasureCodec, ErrorKind, Result};
+
+/// Collects shards in any order and decodes once `data_shards` distinct ones are in.
+///
+/// Any `data_shards` shards suffice for every backend: novelpoly rounds its
+/// `k` down to a power of two and so may be ready earlier, but the session
+/// waits for the common threshold so all backends decode at the same point.
+pub struct DecoderSession<'a> {
+    codec: &'a dyn ErasureCodec,
+    data_shards: usize,
+    parity_shards: usize,
+    data_len: usize,
+    shards: Vec<Option<Vec<u8>>>,
+    received: usize,
+}
+
+impl<'a> DecoderSession<'a> {
+    /// Session for a payload of `data_len` bytes encoded by `codec`.
+    pub fn new(
+        codec: &'a dyn ErasureCodec,
+        data_shards: usize,
+        parity_shards: usize,
+        data_len: usize,
+    ) -> Self {
+        Self {
+            codec,
+            data_shards,
+            parity_shards,
+            data_len,
+            shards: vec![None; data_shards + parity_shards],
+            received: 0,
+        }
+    }
+
+    /// Stores shard `index` and returns whether the session can now decode.
+    ///
+    /// A shard that was already received is ignored, as a network may deliver
+    /// it twice; a different shard for the same index is rejected.
+    pub fn add_shard(&mut self, index: usize, shard: Vec<u8>) -> Result<bool> {
+        let total = self.data_shards + self.parity_shards;
+        let slot = self.shards.get_mut(index).ok_or_else(|| {
+            CodecError::new(
+                ErrorKind::InvalidIndex,
+                format!("shard index {} out of range for {} shards", index, total),
+            )
+        })?;
+        match slot {
+            None => {
+                *slot = Some(shard);
+                self.received += 1;
+            }
+            Some(first) if *first != shard => {
+                return Err(CodecError::new(
+                    ErrorKind::DuplicateIndex,
+                    format!("shard {} received twice with different contents", index),
+                ));
+            }
+            Some(_) => {}
+        }
+        Ok(self.is_ready())
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.received >= self.data_shards
+    }
+
+    /// Number of distinct shards received so far.
+    pub fn received(&self) -> usize {
+        self.received
+    }
+
+    /// Data shards not among those received; decoding has to restore them.
+    pub fn missing_data_shards(&self) -> usize {
+        self.shards[..self.data_shards]
+            .iter()
+            .filter(|shard| shard.is_none())
+            .count()
+    }
+
+    /// Recovers the payload from the shards received so far.
+    pub fn decode(&self) -> Result<Vec<u8>> {
+        if !self.is_ready() {
+            return Err(CodecError::new(
+                ErrorKind::TooFewShards,
+                format!(
+                    "only {} of {} shards needed have arrived",
+                    self.received, self.data_shards
+                ),
+            ));
+        }
+        self.codec.decode(
+            self.data_shards,
+            self.parity_shards,
+            &self.shards,
+            self.data_len,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::backends;
+    use crate::rng::Rng;
+
+    #[test]
+    fn decodes_once_enough_shards_arrive_in_any_order() {
+        let (k, m) = (4, 8);
+        let data: Vec<u8> = (0..5000).map(|i| (i % 253) as u8).collect();
+        for codec in backends() {
+            let shards = codec.encode(k, m, &data).unwrap();
+            let mut order: Vec<usize> = (0..k + m).collect();
+            Rng::new(11).shuffle(&mut order);
+
+            let mut session = DecoderSession::new(codec.as_ref(), k, m, data.len());
+            for &index in &order[..k - 1] {
+                assert!(!session.add_shard(index, shards[index].clone()).unwrap());
+                // Duplicates do not count towards the threshold
+                assert!(!session.add_shard(index, shards[index].clone()).unwrap());
+            }
+            assert!(session.decode().is_err(), "{}", codec.name());
+            assert!(session
+                .add_shard(order[k - 1], shards[order[k - 1]].clone())
+                .unwrap());
+            assert_eq!(session.received(), k);
+            assert!(session.decode().unwrap() == data, "{}", codec.name());
+            assert!(session.add_shard(k + m, Vec::new()).is_err());
+        }
+    }
+}
@@ -0,0 +1,8 @@
+//! reed-solomon-simd adapter.
+
+super::leopard::leopard_codec! {
+    /// `reed_solomon_simd`, an O(n log n) codec over GF(2^16).
+    Simd, reed_solomon_simd, "simd",
+    alignment = 2,
+    map = BTreeMap
+}
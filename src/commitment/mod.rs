@@ -0,0 +1,224 @@
+//! Merkle commitments over encoded shards, with per-shard inclusion proofs.
+//!
+//! The root commits to the whole codeword, so a receiver can check that one
+//! shard belongs to it before using it. Leaves are `SHA-256(0x00 || shard)`
+//! and inner nodes `SHA-256(0x01 || left || right)`, so a leaf can never pass
+//! for an inner node. The last node of an odd-sized level is promoted as is
+//! rather than paired with a copy of itself, which keeps distinct shard lists
+//! from sharing a root.
+
+pub mod sha256;
+
+pub use sha256::Digest;
+use sha256::Sha256;
+
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+fn leaf_hash(shard: &[u8]) -> Digest {
+    let mut hasher = Sha256::new();
+    hasher.update(&[LEAF_PREFIX]);
+    hasher.update(shard);
+    hasher.finalize()
+}
+
+fn node_hash(left: &Digest, right: &Digest) -> Digest {
+    let mut hasher = Sha256::new();
+    hasher.update(&[NODE_PREFIX]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize()
+}
+
+/// Merkle tree over the shards of one codeword, any backend's.
+#[derive(Debug, Clone)]
+pub struct MerkleTree {
+    /// Leaf hashes first, the root alone last.
+    levels: Vec<Vec<Digest>>,
+}
+
+impl MerkleTree {
+    /// Commits to `shards` in index order; an empty list commits to the empty hash.
+    pub fn new<S: AsRef<[u8]>>(shards: &[S]) -> Self {
+        let mut levels = vec![shards
+            .iter()
+            .map(|shard| leaf_hash(shard.as_ref()))
+            .collect::<Vec<_>>()];
+        while levels.last().is_some_and(|level| level.len() > 1) {
+            let below = levels.last().unwrap();
+            let level = below
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => node_hash(left, right),
+                    [single] => *single,
+                    _ => unreachable!("chunks of two"),
+                })
+                .collect();
+            levels.push(level);
+        }
+        Self { levels }
+    }
+
+    pub fn root(&self) -> Digest {
+        match self.levels.last().and_then(|level| level.first()) {
+            Some(root) => *root,
+            None => sha256::digest(&[]),
+        }
+    }
+
+    /// Number of shards committed to.
+    pub fn len(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Inclusion proof for shard `index`; `None` past the last shard.
+    pub fn proof(&self, index: usize) -> Option<Proof> {
+        if index >= self.len() {
+            return None;
+        }
+        let mut siblings = Vec::new();
+        let mut position = index;
+        for level in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = level.get(position ^ 1) {
+                siblings.push(*sibling);
+            }
+            position /= 2;
+        }
+        Some(Proof { index, siblings })
+    }
+}
+
+/// Evidence that a shard sits at `index` in a committed codeword.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Proof {
+    pub index: usize,
+    /// Sibling hashes from the leaf level up; promoted levels contribute none.
+    pub siblings: Vec<Digest>,
+}
+
+impl Proof {
+    /// Whether `shard` hashes up to `root` along this proof, in a codeword of
+    /// `leaves` shards. The count must be the verifier's own: the root does not
+    /// commit to it, and a smaller one lets a shard pass for another index.
+    pub fn verify(&self, root: &Digest, leaves: usize, shard: &[u8]) -> bool {
+        if self.index >= leaves {
+            return false;
+        }
+        let mut hash = leaf_hash(shard);
+        let mut siblings = self.siblings.iter();
+        let (mut position, mut width) = (self.index, leaves);
+        while width > 1 {
+            if position ^ 1 < width {
+                let Some(sibling) = siblings.next() else {
+                    return false;
+                };
+                hash = if position % 2 == 0 {
+                    node_hash(&hash, sibling)
+                } else {
+                    node_hash(sibling, &hash)
+                };
+            }
+            position /= 2;
+            width = width.div_ceil(2);
+        }
+        siblings.next().is_none() && hash == *root
+    }
+
+    /// Bytes a proof adds to a shard on the wire: the sibling hashes.
+    pub fn encoded_len(&self) -> usize {
+        self.siblings.len() * std::mem::size_of::<Digest>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec;
+    use crate::config::BenchConfig;
+
+    #[test]
+    fn proofs_verify_for_every_shard_of_every_backend() {
+        let config = BenchConfig::new(5, 10_000);
+        let data: Vec<u8> = (0..config.data_size).map(|i| (i % 239) as u8).collect();
+        for codec in codec::backends() {
+            let shards = codec
+                .encode(config.data_shards(), config.coding_shards(), &data)
+                .unwrap();
+            let tree = MerkleTree::new(&shards);
+            let root = tree.root();
+            assert_eq!(tree.len(), config.total_shards());
+            for (index, shard) in shards.iter().enumerate() {
+                let proof = tree.proof(index).unwrap();
+                assert!(
+                    proof.verify(&root, shards.len(), shard),
+                    "{} {}",
+                    codec.name(),
+                    index
+                );
+
+                // Wrong shard, wrong position, wrong root
+                let mut corrupted = shard.clone();
+                corrupted[0] ^= 1;
+                assert!(!proof.verify(&root, shards.len(), &corrupted));
+                let other = (index + 1) % shards.len();
+                assert!(
+                    !tree
+                        .proof(other)
+                        .unwrap()
+                        .verify(&root, shards.len(), shard)
+                        || shards[other] == *shard
+                );
+                assert!(!proof.verify(&[0; 32], shards.len(), shard));
+            }
+            assert!(tree.proof(shards.len()).is_none());
+        }
+    }
+
+    #[test]
+    fn odd_and_tiny_trees() {
+        for leaves in 1..=9usize {
+            let shards: Vec<Vec<u8>> = (0..leaves).map(|i| vec![i as u8; 3]).collect();
+            let tree = MerkleTree::new(&shards);
+            for (index, shard) in shards.iter().enumerate() {
+                let proof = tree.proof(index).unwrap();
+                assert!(
+                    proof.verify(&tree.root(), leaves, shard),
+                    "{} of {}",
+                    index,
+                    leaves
+                );
+                // Truncated and padded proofs are rejected
+                let mut short = proof.clone();
+                if short.siblings.pop().is_some() {
+                    assert!(!short.verify(&tree.root(), leaves, shard));
+                }
+                let mut long = proof.clone();
+                long.siblings.push([0; 32]);
+                assert!(!long.verify(&tree.root(), leaves, shard));
+            }
+        }
+        // A duplicated last shard changes the root
+        let three = MerkleTree::new(&[b"a", b"b", b"c"]);
+        let four = MerkleTree::new(&[b"a", b"b", b"c", b"c"]);
+        assert_ne!(three.root(), four.root());
+        assert_eq!(MerkleTree::new::<Vec<u8>>(&[]).root(), sha256::digest(&[]));
+    }
+
+    #[test]
+    fn a_proof_cannot_shrink_the_tree_to_move_a_shard() {
+        // With 3 leaves the root is H(H(h0, h1), h2): shard 2's one sibling,
+        // read as index 1 of a 2-leaf tree, hashes up to the same root
+        let shards = [b"a", b"b", b"c"];
+        let tree = MerkleTree::new(&shards);
+        let forged = Proof {
+            index: 1,
+            siblings: tree.proof(2).unwrap().siblings,
+        };
+        assert!(forged.verify(&tree.root(), 2, shards[2]));
+        assert!(!forged.verify(&tree.root(), 3, shards[2]));
+    }
+}
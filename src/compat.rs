@@ -0,0 +1,220 @@
+//! Systematic-ness and cross-backend compatibility of the adapters' codewords.
+//!
+//! A backend is systematic here when its data shards are the payload split
+//! into `k` equal, zero-padded pieces, byte for byte. Two backends are
+//! interchangeable when they produce the same parity for the same payload;
+//! then shards encoded by one can be decoded by the other. Every pair is
+//! checked by actually doing that.
+
+use crate::codec::{self, split_shards, ErasureCodec};
+use crate::config::BenchConfig;
+use crate::data::Content;
+use crate::harness::Operation;
+
+/// How one backend's parity compares to another's over the configs both run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Parity {
+    /// Byte-identical parity in every config.
+    Identical,
+    /// Same shard count and sizes, different bytes in some config.
+    Differs,
+    /// Different shard count or sizes in some config, so bytes cannot be compared.
+    Shape,
+    /// No config that both backends support.
+    Untested,
+}
+
+impl Parity {
+    pub fn name(self) -> &'static str {
+        match self {
+            Parity::Identical => "identical",
+            Parity::Differs => "differs",
+            Parity::Shape => "shape",
+            Parity::Untested => "untested",
+        }
+    }
+}
+
+/// Shards encoded by `encoder`, decoded by `decoder`.
+#[derive(Debug, Clone, Copy)]
+pub struct Pair {
+    pub encoder: &'static str,
+    pub decoder: &'static str,
+    pub parity: Parity,
+    /// Whether `decoder` recovered the payload from `encoder`'s shards with
+    /// the first F missing, in every config where the shard shapes match;
+    /// `None` if there was none.
+    pub decodes: Option<bool>,
+}
+
+/// Systematic-ness of every backend and every ordered pair of backends.
+#[derive(Debug, Clone)]
+pub struct Compatibility {
+    pub backends: Vec<&'static str>,
+    /// Per backend, in `backends` order; `None` if it supports no config.
+    pub systematic: Vec<Option<bool>>,
+    /// Row-major: `pairs[encoder * backends.len() + decoder]`.
+    pub pairs: Vec<Pair>,
+}
+
+impl Compatibility {
+    pub fn pair(&self, encoder: usize, decoder: usize) -> &Pair {
+        &self.pairs[encoder * self.backends.len() + decoder]
+    }
+}
+
+/// Whether the data shards of `shards` are `data` split as the adapters split it.
+fn is_systematic(data: &[u8], data_shards: usize, shards: &[Vec<u8>]) -> bool {
+    let Some(shard_len) = shards.first().map(Vec::len) else {
+        return false;
+    };
+    // The adapters' split, at whatever shard length this backend chose
+    shard_len * data_shards >= data.len()
+        && shards.len() >= data_shards
+        && split_shards(data, data_shards, shard_len) == shards[..data_shards]
+}
+
+/// Checks every backend, and every ordered pair, over `configs`.
+///
+/// Payloads are seeded uniform bytes, sized `k` KiB so that every backend's
+/// shard alignment is met without padding.
+pub fn check(
+    codecs: &[Box<dyn ErasureCodec>],
+    configs: &[BenchConfig],
+    seed: u64,
+) -> codec::Result<Compatibility> {
+    let configs: Vec<BenchConfig> = configs
+        .iter()
+        .map(|config| BenchConfig::new(config.f, config.data_shards() * 1024))
+        .collect();
+    // Shards per backend per config, `None` where unsupported
+    let mut encoded: Vec<Vec<Option<Vec<Vec<u8>>>>> = Vec::with_capacity(codecs.len());
+    let mut payloads = Vec::with_capacity(configs.len());
+    for config in &configs {
+        payloads.push(Content::Uniform.generate(config.data_size, seed)?);
+    }
+    for codec in codecs {
+        let mut per_config = Vec::with_capacity(configs.len());
+        for (config, data) in configs.iter().zip(&payloads) {
+            let (k, m) = (config.data_shards(), config.coding_shards());
+            per_config.push(match codec.supports(k, m) {
+                true => Some(codec.encode(k, m, data)?),
+                false => None,
+            });
+        }
+        encoded.push(per_config);
+    }
+
+    let systematic = encoded
+        .iter()
+        .map(|per_config| {
+            let tried: Vec<bool> = per_config
+                .iter()
+                .zip(configs.iter().zip(&payloads))
+                .filter_map(|(shards, (config, data))| {
+                    Some(is_systematic(data, config.data_shards(), shards.as_ref()?))
+                })
+                .collect();
+            (!tried.is_empty()).then(|| tried.iter().all(|&verbatim| verbatim))
+        })
+        .collect();
+
+    let mut pairs = Vec::with_capacity(codecs.len() * codecs.len());
+    for (a, encoder) in codecs.iter().enumerate() {
+        for (b, decoder) in codecs.iter().enumerate() {
+            let mut parity = Parity::Untested;
+            let mut decodes = None;
+            for (index, (config, data)) in configs.iter().zip(&payloads).enumerate() {
+                let (Some(ours), Some(theirs)) = (&encoded[a][index], &encoded[b][index]) else {
+                    continue;
+                };
+                let k = config.data_shards();
+                let same_shape = ours.len() == theirs.len()
+                    && ours.iter().zip(theirs).all(|(x, y)| x.len() == y.len());
+                if !same_shape {
+                    // A decoder cannot be fed shards of a layout it never produces
+                    parity = Parity::Shape;
+                    continue;
+                }
+                let this = if ours[k..] == theirs[k..] {
+                    Parity::Identical
+                } else {
+                    Parity::Differs
+                };
+                // The least favourable outcome across configs wins
+                parity = match parity {
+                    Parity::Shape | Parity::Differs => parity,
+                    Parity::Identical | Parity::Untested => this,
+                };
+                let ok = cross_decode(decoder.as_ref(), config, ours, data);
+                decodes = Some(decodes.unwrap_or(true) && ok);
+            }
+            pairs.push(Pair {
+                encoder: encoder.name(),
+                decoder: decoder.name(),
+                parity,
+                decodes,
+            });
+        }
+    }
+
+    Ok(Compatibility {
+        backends: codecs.iter().map(|codec| codec.name()).collect(),
+        systematic,
+        pairs,
+    })
+}
+
+/// Whether `decoder` recovers `data` from `shards` with the first F withheld.
+fn cross_decode(
+    decoder: &dyn ErasureCodec,
+    config: &BenchConfig,
+    shards: &[Vec<u8>],
+    data: &[u8],
+) -> bool {
+    let missing = Operation::DecodeFMissing.missing_indices(config);
+    let received: Vec<Option<Vec<u8>>> = shards
+        .iter()
+        .enumerate()
+        .map(|(i, shard)| (!missing.contains(&i)).then(|| shard.clone()))
+        .collect();
+    let (k, m) = (config.data_shards(), config.coding_shards());
+    decoder
+        .decode(k, m, &received, data.len())
+        .is_ok_and(|decoded| decoded == data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::backends;
+
+    #[test]
+    fn every_backend_is_compatible_with_itself() {
+        let codecs = backends();
+        let configs = [BenchConfig::new(1, 0), BenchConfig::new(4, 0)];
+        let compat = check(&codecs, &configs, 3).unwrap();
+        for (i, name) in compat.backends.iter().enumerate() {
+            let pair = compat.pair(i, i);
+            assert_eq!(pair.parity, Parity::Identical, "{}", name);
+            assert_eq!(pair.decodes, Some(true), "{}", name);
+            let expected = *name != "novelpoly";
+            assert_eq!(compat.systematic[i], Some(expected), "{}", name);
+        }
+    }
+
+    #[cfg(all(feature = "rs16", feature = "simd"))]
+    #[test]
+    fn rs16_and_simd_share_codewords() {
+        let codecs: Vec<_> = ["rs16", "simd"]
+            .iter()
+            .map(|name| codec::backend(name).unwrap())
+            .collect();
+        let configs = [BenchConfig::new(2, 0), BenchConfig::new(10, 0)];
+        let compat = check(&codecs, &configs, 5).unwrap();
+        for pair in &compat.pairs {
+            assert_eq!(pair.parity, Parity::Identical, "{:?}", pair);
+            assert_eq!(pair.decodes, Some(true), "{:?}", pair);
+        }
+    }
+}
@@ -0,0 +1,146 @@
+//! Benchmark parameter space shared by the bench targets and the CLI.
+
+/// Configuration for a Reed-Solomon test
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// F value (data shards = F, coding shards = 2F)
+    pub f: usize,
+    /// Data size in bytes
+    pub data_size: usize,
+}
+
+impl BenchConfig {
+    pub fn new(f: usize, data_size: usize) -> Self {
+        Self { f, data_size }
+    }
+
+    pub fn data_shards(&self) -> usize {
+        self.f
+    }
+
+    pub fn coding_shards(&self) -> usize {
+        2 * self.f
+    }
+
+    pub fn total_shards(&self) -> usize {
+        3 * self.f
+    }
+
+    pub fn shard_size(&self) -> usize {
+        let size: usize = (self.data_size + self.f - 1) / self.f;
+        // Round up to nearest multiple of 64
+        ((size + 63) / 64) * 64
+    }
+
+    /// Zero bytes [`shard_size`](Self::shard_size) adds across the data shards
+    pub fn padding(&self) -> usize {
+        self.data_shards() * self.shard_size() - self.data_size
+    }
+}
+
+/// All F values to test
+pub const F_VALUES: &[usize] = &[1, 2, 3, 5, 10, 20, 33, 66];
+
+/// All data sizes to test (in bytes)
+pub const DATA_SIZES: &[usize] = &[
+    256 * 1024, // 256KB
+    512 * 1024, // 512KB
+    1024 * 1024, // 1MB
+
+                // 1024,              // 1KB
+                // 4 * 1024,        // 4KB
+                // 16 * 1024,       // 16KB
+                // 64 * 1024,       // 64KB
+                // 2 * 1024 * 1024, // 2MB
+                // 4 * 1024 * 1024, // 4MB
+                // 16 * 1024 * 1024,  // 16MB
+                // 64 * 1024 * 1024,  // 64MB
+                // 100 * 1024 * 1024, // 100MB
+];
+
+/// Data sizes of the small-message tier: consensus votes and certificates
+/// (in bytes), where fixed per-call overhead outweighs the coding itself
+pub const SMALL_DATA_SIZES: &[usize] = &[
+    64,        // 64B
+    256,       // 256B
+    1024,      // 1KB
+    4 * 1024,  // 4KB
+    16 * 1024, // 16KB
+    64 * 1024, // 64KB
+];
+
+/// F values of the large-committee tier: 501 to 3000 nodes (n = 3F)
+pub const LARGE_F_VALUES: &[usize] = &[167, 334, 500, 667, 1000];
+
+/// Data sizes of the large-committee tier (in bytes)
+pub const LARGE_DATA_SIZES: &[usize] = &[
+    1024 * 1024,     // 1MB
+    4 * 1024 * 1024, // 4MB
+];
+
+/// F values of the content axis, which repeats each configuration per content
+pub const CONTENT_F_VALUES: &[usize] = &[10, 33];
+
+/// Data sizes of the content axis (in bytes)
+pub const CONTENT_DATA_SIZES: &[usize] = &[
+    1024 * 1024, // 1MB
+];
+
+/// Original widths `k` of the 2D extension, which turns a k×k square of
+/// shares into a 2k×2k one
+pub const SQUARE_WIDTHS: &[usize] = &[4, 8, 16, 32, 64, 128];
+
+/// Bytes per share of the 2D extension, Celestia's share size
+pub const SHARE_SIZE: usize = 512;
+
+/// Payload sizes that exercise shard padding and truncation: none is a multiple
+/// of 64 and 1000003 is prime, so no F > 1 divides it.
+pub const UNALIGNED_DATA_SIZES: &[usize] = &[1, 63, 1000, 65_537, 1_000_003];
+
+/// Format data size for display
+pub fn format_size(size: usize) -> String {
+    // Sizes that are not whole units are printed in bytes, so labels stay unique
+    if size >= 1024 * 1024 && size.is_multiple_of(1024 * 1024) {
+        format!("{}MB", size / (1024 * 1024))
+    } else if size >= 1024 && size.is_multiple_of(1024) {
+        format!("{}KB", size / 1024)
+    } else {
+        format!("{}B", size)
+    }
+}
+
+/// Get all benchmark configurations
+pub fn all_configs() -> Vec<BenchConfig> {
+    configs(F_VALUES, DATA_SIZES)
+}
+
+/// Configurations over [`UNALIGNED_DATA_SIZES`]
+pub fn unaligned_configs() -> Vec<BenchConfig> {
+    configs(F_VALUES, UNALIGNED_DATA_SIZES)
+}
+
+/// Configurations of the small-message tier
+pub fn small_configs() -> Vec<BenchConfig> {
+    configs(F_VALUES, SMALL_DATA_SIZES)
+}
+
+/// Configurations swept once per payload content
+pub fn content_configs() -> Vec<BenchConfig> {
+    configs(CONTENT_F_VALUES, CONTENT_DATA_SIZES)
+}
+
+/// Configurations of the large-committee tier
+pub fn large_configs() -> Vec<BenchConfig> {
+    configs(LARGE_F_VALUES, LARGE_DATA_SIZES)
+}
+
+/// Every combination of the given F values and data sizes
+pub fn configs(f_values: &[usize], data_sizes: &[usize]) -> Vec<BenchConfig> {
+    let mut configs = Vec::new();
+    for &f in f_values {
+        for &data_size in data_sizes {
+            configs.push(BenchConfig::new(f, data_size));
+        }
+    }
+    configs
+}
@@ -0,0 +1,181 @@
+//! Shard container: the framing a shard travels in between nodes.
+//!
+//! A fixed 36-byte little-endian header followed by the shard bytes:
+//!
+//! | offset | size | field           |
+//! |--------|------|-----------------|
+//! | 0      | 4    | magic `RSC1`    |
+//! | 4      | 8    | message id      |
+//! | 12     | 4    | data shards     |
+//! | 16     | 4    | parity shards   |
+//! | 20     | 4    | shard index     |
+//! | 24     | 4    | shard length    |
+//! | 28     | 8    | payload length  |
+//!
+//! The header carries everything a receiver needs to decode without knowing
+//! the sender's configuration, except which backend encoded the shards.
+
+use std::io::{self, Read, Write};
+
+/// Identifies the format and its version.
+pub const MAGIC: [u8; 4] = *b"RSC1";
+
+/// Bytes preceding the shard in every container.
+pub const HEADER_LEN: usize = 36;
+
+/// Largest shard a reader accepts, so a corrupt length cannot exhaust memory.
+pub const MAX_SHARD_LEN: usize = 1 << 30;
+
+/// Metadata framed with each shard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShardHeader {
+    /// Identifies the payload the shard belongs to.
+    pub message: u64,
+    pub data_shards: u32,
+    pub parity_shards: u32,
+    pub index: u32,
+    pub shard_len: u32,
+    /// Length of the encoded payload, for stripping the padding on decode.
+    pub data_len: u64,
+}
+
+impl ShardHeader {
+    pub fn to_bytes(&self) -> [u8; HEADER_LEN] {
+        let mut bytes = [0u8; HEADER_LEN];
+        bytes[0..4].copy_from_slice(&MAGIC);
+        bytes[4..12].copy_from_slice(&self.message.to_le_bytes());
+        bytes[12..16].copy_from_slice(&self.data_shards.to_le_bytes());
+        bytes[16..20].copy_from_slice(&self.parity_shards.to_le_bytes());
+        bytes[20..24].copy_from_slice(&self.index.to_le_bytes());
+        bytes[24..28].copy_from_slice(&self.shard_len.to_le_bytes());
+        bytes[28..36].copy_from_slice(&self.data_len.to_le_bytes());
+        bytes
+    }
+
+    /// Parses a header, rejecting a wrong magic or inconsistent fields.
+    pub fn from_bytes(bytes: &[u8; HEADER_LEN]) -> io::Result<Self> {
+        if bytes[0..4] != MAGIC {
+            return Err(invalid("not a shard container"));
+        }
+        let u32_at = |at: usize| u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap());
+        let u64_at = |at: usize| u64::from_le_bytes(bytes[at..at + 8].try_into().unwrap());
+        let header = Self {
+            message: u64_at(4),
+            data_shards: u32_at(12),
+            parity_shards: u32_at(16),
+            index: u32_at(20),
+            shard_len: u32_at(24),
+            data_len: u64_at(28),
+        };
+        let total = header.data_shards as u64 + header.parity_shards as u64;
+        if header.index as u64 >= total {
+            return Err(invalid("shard index out of range"));
+        }
+        if header.shard_len as usize > MAX_SHARD_LEN {
+            return Err(invalid("shard length exceeds the container limit"));
+        }
+        Ok(header)
+    }
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// Writes one container; the header's `shard_len` is taken from `shard`.
+pub fn write_shard<W: Write + ?Sized>(
+    writer: &mut W,
+    header: &ShardHeader,
+    shard: &[u8],
+) -> io::Result<()> {
+    let shard_len = u32::try_from(shard.len()).map_err(|_| invalid("shard too long"))?;
+    let header = ShardHeader {
+        shard_len,
+        ..*header
+    };
+    writer.write_all(&header.to_bytes())?;
+    writer.write_all(shard)
+}
+
+/// Reads one container; `Ok(None)` on a clean end of stream between containers.
+pub fn read_shard<R: Read + ?Sized>(reader: &mut R) -> io::Result<Option<(ShardHeader, Vec<u8>)>> {
+    let mut bytes = [0u8; HEADER_LEN];
+    // A stream that ends before the first header byte is a clean close
+    loop {
+        match reader.read(&mut bytes[..1]) {
+            Ok(0) => return Ok(None),
+            Ok(_) => break,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        }
+    }
+    reader.read_exact(&mut bytes[1..])?;
+    let header = ShardHeader::from_bytes(&bytes)?;
+    let mut shard = vec![0u8; header.shard_len as usize];
+    reader.read_exact(&mut shard)?;
+    Ok(Some((header, shard)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header() -> ShardHeader {
+        ShardHeader {
+            message: 7,
+            data_shards: 3,
+            parity_shards: 6,
+            index: 8,
+            shard_len: 0,
+            data_len: 1000,
+        }
+    }
+
+    #[test]
+    fn round_trips_through_a_stream() {
+        let mut wire = Vec::new();
+        write_shard(&mut wire, &header(), &[1, 2, 3]).unwrap();
+        write_shard(
+            &mut wire,
+            &ShardHeader {
+                index: 0,
+                ..header()
+            },
+            &[],
+        )
+        .unwrap();
+        assert_eq!(wire.len(), 2 * HEADER_LEN + 3);
+
+        let mut reader = wire.as_slice();
+        let (first, shard) = read_shard(&mut reader).unwrap().unwrap();
+        assert_eq!(
+            first,
+            ShardHeader {
+                shard_len: 3,
+                ..header()
+            }
+        );
+        assert_eq!(shard, [1, 2, 3]);
+        let (second, shard) = read_shard(&mut reader).unwrap().unwrap();
+        assert_eq!((second.index, shard.len()), (0, 0));
+        assert!(read_shard(&mut reader).unwrap().is_none());
+    }
+
+    #[test]
+    fn rejects_malformed_containers() {
+        let mut wire = Vec::new();
+        write_shard(&mut wire, &header(), &[1, 2, 3]).unwrap();
+
+        let mut bad_magic = wire.clone();
+        bad_magic[0] = b'X';
+        assert!(read_shard(&mut bad_magic.as_slice()).is_err());
+
+        let mut bad_index = wire.clone();
+        bad_index[20] = 9;
+        assert!(read_shard(&mut bad_index.as_slice()).is_err());
+
+        // Truncated in the header and in the shard
+        assert!(read_shard(&mut &wire[..10]).is_err());
+        assert!(read_shard(&mut &wire[..HEADER_LEN + 1]).is_err());
+    }
+}
@@ -0,0 +1,153 @@
+//! Payload generators, so codec speed can be checked against content.
+
+use crate::rng::Rng;
+use std::io;
+use std::path::PathBuf;
+
+/// Seed used when none is given on the command line.
+pub const DEFAULT_SEED: u64 = 0x5EED;
+
+/// In sparse payloads, one byte in this many is non-zero on average.
+const SPARSE_ONE_IN: u64 = 64;
+
+/// Payload content; each kind is one value of the sweep's content axis.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Content {
+    /// `i % 256`, the pattern used by the divan benches.
+    Ramp,
+    /// Uniformly random bytes.
+    Uniform,
+    /// All `0x00`; catches backends that shortcut zero blocks.
+    Zeros,
+    /// All `0xFF`.
+    Ones,
+    /// Mostly zero, with random non-zero bytes scattered through.
+    Sparse,
+    /// Bytes of a real file, repeated or truncated to the requested size.
+    File(PathBuf),
+}
+
+impl Content {
+    /// The content kinds that need no external input.
+    pub const SYNTHETIC: &'static [Content] = &[
+        Content::Ramp,
+        Content::Uniform,
+        Content::Zeros,
+        Content::Ones,
+        Content::Sparse,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Content::Ramp => "ramp",
+            Content::Uniform => "uniform",
+            Content::Zeros => "zeros",
+            Content::Ones => "ones",
+            Content::Sparse => "sparse",
+            Content::File(_) => "file",
+        }
+    }
+
+    /// Parses a content name; `file:PATH` selects a file-sourced payload.
+    pub fn from_name(name: &str) -> Option<Self> {
+        if let Some(path) = name.strip_prefix("file:") {
+            return Some(Content::File(PathBuf::from(path)));
+        }
+        Self::SYNTHETIC.iter().find(|c| c.name() == name).cloned()
+    }
+
+    /// Generates `size` bytes; the same seed always yields the same payload.
+    pub fn generate(&self, size: usize, seed: u64) -> io::Result<Vec<u8>> {
+        let mut rng = Rng::new(seed);
+        Ok(match self {
+            Content::Ramp => (0..size).map(|i| (i % 256) as u8).collect(),
+            Content::Uniform => {
+                let mut data = vec![0u8; size];
+                rng.fill_bytes(&mut data);
+                data
+            }
+            Content::Zeros => vec![0x00; size],
+            Content::Ones => vec![0xFF; size],
+            Content::Sparse => (0..size)
+                .map(|_| match rng.below(SPARSE_ONE_IN) {
+                    0 => rng.below(255) as u8 + 1,
+                    _ => 0,
+                })
+                .collect(),
+            Content::File(path) => {
+                let source = std::fs::read(path)?;
+                if source.is_empty() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("{} is empty", path.display()),
+                    ));
+                }
+                source.iter().copied().cycle().take(size).collect()
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec;
+
+    #[test]
+    fn generators_are_deterministic() {
+        for content in Content::SYNTHETIC {
+            assert_eq!(
+                content.generate(4096, 1).unwrap(),
+                content.generate(4096, 1).unwrap()
+            );
+        }
+        assert_ne!(
+            Content::Uniform.generate(64, 1).unwrap(),
+            Content::Uniform.generate(64, 2).unwrap()
+        );
+    }
+
+    #[test]
+    fn generators_have_expected_content() {
+        assert!(Content::Zeros
+            .generate(100, 0)
+            .unwrap()
+            .iter()
+            .all(|&b| b == 0));
+        assert!(Content::Ones
+            .generate(100, 0)
+            .unwrap()
+            .iter()
+            .all(|&b| b == 0xFF));
+
+        let sparse = Content::Sparse.generate(1 << 16, 0).unwrap();
+        let non_zero = sparse.iter().filter(|&&b| b != 0).count();
+        assert!(non_zero > 0 && non_zero < sparse.len() / 16);
+    }
+
+    #[test]
+    fn file_content_repeats_source() {
+        let path = std::env::temp_dir().join("rs-bench-data-source.bin");
+        std::fs::write(&path, b"abc").unwrap();
+        let content = Content::from_name(&format!("file:{}", path.display())).unwrap();
+        assert_eq!(content.generate(7, 0).unwrap(), b"abcabca");
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn every_content_round_trips() {
+        for content in Content::SYNTHETIC {
+            let data = content.generate(10_000, DEFAULT_SEED).unwrap();
+            for codec in codec::backends() {
+                let shards = codec.encode(3, 6, &data).unwrap();
+                let received: Vec<_> = shards
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, shard)| (i >= 6).then_some(shard))
+                    .collect();
+                let decoded = codec.decode(3, 6, &received, data.len()).unwrap();
+                assert_eq!(decoded, data, "{}", codec.name());
+            }
+        }
+    }
+}
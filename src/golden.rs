@@ -0,0 +1,270 @@
+//! Golden vectors: pinned codewords that catch upstream behaviour changes.
+//!
+//! Shards written by one version of a backend crate must stay decodable by the
+//! next. For a few seeded payloads per backend, `golden/vectors.txt` records
+//! the shard layout and SHA-256 digests of the data and parity shards. Any
+//! drift, say a minor release that reorders its parity, fails the tests until
+//! the vectors are regenerated on purpose with
+//! `cargo run -- golden --regenerate golden/vectors.txt`.
+
+use crate::codec::{self, ErasureCodec};
+use crate::commitment::sha256::{Digest, Sha256};
+use crate::config::{self, BenchConfig};
+use crate::data::Content;
+
+/// The checked-in vectors.
+pub const VECTORS: &str = include_str!("../golden/vectors.txt");
+
+/// Seed of the uniform payloads the vectors are computed from.
+pub const SEED: u64 = 0x601d;
+
+/// F values pinned per backend; small enough that every backend supports them.
+pub const F_VALUES: &[usize] = &[1, 2, 5, 10, 33];
+
+/// One unaligned size, so the padding is pinned too, and one aligned size.
+pub const DATA_SIZES: &[usize] = &[1000, 65_536];
+
+/// Every configuration pinned per backend.
+pub fn configs() -> Vec<BenchConfig> {
+    config::configs(F_VALUES, DATA_SIZES)
+}
+
+/// The codeword one backend produced for one seeded payload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Vector {
+    pub backend: String,
+    pub config: BenchConfig,
+    pub seed: u64,
+    pub shards: usize,
+    pub shard_len: usize,
+    /// SHA-256 over the first `k` shards, concatenated.
+    pub data: Digest,
+    /// SHA-256 over the remaining shards, concatenated.
+    pub parity: Digest,
+}
+
+impl Vector {
+    /// Encodes the seeded payload for `config` with `codec`.
+    pub fn compute(
+        codec: &dyn ErasureCodec,
+        config: &BenchConfig,
+        seed: u64,
+    ) -> codec::Result<Self> {
+        let (k, m) = (config.data_shards(), config.coding_shards());
+        let data = Content::Uniform.generate(config.data_size, seed)?;
+        let shards = codec.encode(k, m, &data)?;
+        let digest = |shards: &[Vec<u8>]| {
+            let mut hasher = Sha256::new();
+            for shard in shards {
+                hasher.update(shard);
+            }
+            hasher.finalize()
+        };
+        Ok(Self {
+            backend: codec.name().to_string(),
+            config: *config,
+            seed,
+            shards: shards.len(),
+            shard_len: shards.first().map_or(0, Vec::len),
+            data: digest(&shards[..k.min(shards.len())]),
+            parity: digest(&shards[k.min(shards.len())..]),
+        })
+    }
+
+    fn is_for(&self, backend: &str, config: &BenchConfig) -> bool {
+        self.backend == backend && self.config == *config
+    }
+
+    fn line(&self) -> String {
+        format!(
+            "{} {} {} {} {} {} {} {}",
+            self.backend,
+            self.config.f,
+            self.config.data_size,
+            self.seed,
+            self.shards,
+            self.shard_len,
+            hex(&self.data),
+            hex(&self.parity)
+        )
+    }
+
+    fn parse_line(line: &str) -> Option<Self> {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let [backend, f, data_size, seed, shards, shard_len, data, parity] = fields[..] else {
+            return None;
+        };
+        Some(Self {
+            backend: backend.to_string(),
+            config: BenchConfig::new(f.parse().ok()?, data_size.parse().ok()?),
+            seed: seed.parse().ok()?,
+            shards: shards.parse().ok()?,
+            shard_len: shard_len.parse().ok()?,
+            data: unhex(data)?,
+            parity: unhex(parity)?,
+        })
+    }
+}
+
+/// Parses a vectors file; blank lines and `#` comments are skipped.
+pub fn parse(text: &str) -> codec::Result<Vec<Vector>> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(|(number, line)| {
+            Vector::parse_line(line).ok_or_else(|| {
+                format!("golden vectors line {}: malformed `{}`", number + 1, line).into()
+            })
+        })
+        .collect()
+}
+
+/// Renders `vectors` in the format [`parse`] reads, with an explanatory header.
+pub fn render(vectors: &[Vector]) -> String {
+    let mut out = String::from(
+        "# Golden codewords, one per backend and configuration.\n\
+         # Regenerate only for an intended change: cargo run -- golden --regenerate golden/vectors.txt\n\
+         # backend f data_size seed shards shard_len sha256(data shards) sha256(parity shards)\n",
+    );
+    for vector in vectors {
+        out.push_str(&vector.line());
+        out.push('\n');
+    }
+    out
+}
+
+/// One backend and configuration compared against its golden vector.
+#[derive(Debug, Clone)]
+pub struct Check {
+    /// `None` when no vector was recorded for this backend and configuration.
+    pub expected: Option<Vector>,
+    pub actual: Vector,
+}
+
+impl Check {
+    pub fn passed(&self) -> bool {
+        self.expected.as_ref() == Some(&self.actual)
+    }
+
+    /// `ok`, `missing`, or what drifted.
+    pub fn status(&self) -> &'static str {
+        match &self.expected {
+            None => "missing",
+            Some(expected) if expected.shards != self.actual.shards => "drift: shard count",
+            Some(expected) if expected.shard_len != self.actual.shard_len => "drift: shard size",
+            Some(expected) if expected.data != self.actual.data => "drift: data shards",
+            Some(expected) if expected.parity != self.actual.parity => "drift: parity shards",
+            Some(_) => "ok",
+        }
+    }
+}
+
+/// Recomputes every pinned configuration `codecs` support and compares it
+/// against `vectors`.
+pub fn check(codecs: &[Box<dyn ErasureCodec>], vectors: &[Vector]) -> codec::Result<Vec<Check>> {
+    let mut checks = Vec::new();
+    for codec in codecs {
+        for config in configs() {
+            if !codec.supports(config.data_shards(), config.coding_shards()) {
+                continue;
+            }
+            let expected = vectors
+                .iter()
+                .find(|vector| vector.is_for(codec.name(), &config))
+                .cloned();
+            let seed = expected.as_ref().map_or(SEED, |vector| vector.seed);
+            checks.push(Check {
+                actual: Vector::compute(codec.as_ref(), &config, seed)?,
+                expected,
+            });
+        }
+    }
+    Ok(checks)
+}
+
+/// Fresh vectors for `codecs`, keeping those in `existing` for any other
+/// backend, so a build without some features does not drop their vectors.
+pub fn regenerate(
+    codecs: &[Box<dyn ErasureCodec>],
+    existing: &[Vector],
+) -> codec::Result<Vec<Vector>> {
+    let mut vectors: Vec<Vector> = existing
+        .iter()
+        .filter(|vector| !codecs.iter().any(|codec| codec.name() == vector.backend))
+        .cloned()
+        .collect();
+    for codec in codecs {
+        for config in configs() {
+            if codec.supports(config.data_shards(), config.coding_shards()) {
+                vectors.push(Vector::compute(codec.as_ref(), &config, SEED)?);
+            }
+        }
+    }
+    vectors.sort_by(|a, b| {
+        (&a.backend, a.config.f, a.config.data_size).cmp(&(
+            &b.backend,
+            b.config.f,
+            b.config.data_size,
+        ))
+    });
+    Ok(vectors)
+}
+
+fn hex(digest: &Digest) -> String {
+    digest.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn unhex(text: &str) -> Option<Digest> {
+    let mut digest = [0u8; 32];
+    if text.len() != 2 * digest.len() {
+        return None;
+    }
+    for (byte, pair) in digest.iter_mut().zip(text.as_bytes().chunks(2)) {
+        *byte = u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok()?;
+    }
+    Some(digest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::backends;
+
+    #[test]
+    fn every_backend_matches_its_golden_vectors() {
+        let vectors = parse(VECTORS).unwrap();
+        for check in check(&backends(), &vectors).unwrap() {
+            assert!(
+                check.passed(),
+                "{} F={} {}B: {}; regenerate golden/vectors.txt only if the change is intended",
+                check.actual.backend,
+                check.actual.config.f,
+                check.actual.config.data_size,
+                check.status()
+            );
+        }
+    }
+
+    #[test]
+    fn vectors_round_trip_and_flag_drift() {
+        let codecs = backends();
+        let Some(codec) = codecs.first() else {
+            return;
+        };
+        let vectors = regenerate(&codecs[..1], &[]).unwrap();
+        assert_eq!(parse(&render(&vectors)).unwrap(), vectors);
+        assert!(parse("erasure 1 1000 x").is_err());
+
+        let mut drifted = vectors.clone();
+        drifted[0].parity[0] ^= 1;
+        let checks = check(&codecs[..1], &drifted).unwrap();
+        assert_eq!(
+            checks[0].status(),
+            "drift: parity shards",
+            "{}",
+            codec.name()
+        );
+        assert!(checks[1..].iter().all(Check::passed));
+        assert_eq!(check(&codecs[..1], &[]).unwrap()[0].status(), "missing");
+    }
+}
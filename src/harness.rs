@@ -0,0 +1,362 @@
+//! Sample-by-sample timing of adapter operations, for reports divan cannot produce.
+
+use crate::cache::Scrubber;
+use crate::codec::{self, aligned_shard_size, split_shards, ErasureCodec};
+use crate::commitment::MerkleTree;
+use crate::config::BenchConfig;
+use crate::tsc;
+use std::fmt;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Iterations run before recording, to settle allocations and branch predictors.
+const WARMUP_ITERATIONS: usize = 10;
+
+/// Operation timed by the harness; mirrors the divan bench functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    Encode,
+    Decode1Missing,
+    DecodeFMissing,
+    Decode2fMissing,
+    /// Like the decodes, but rebuilds the missing parity as well as the data.
+    Reconstruct1Missing,
+    ReconstructFMissing,
+    Reconstruct2fMissing,
+    /// Regenerates only the lost shards, taken from the parity end as when parity nodes die.
+    Repair1Missing,
+    RepairFMissing,
+    Repair2fMissing,
+    /// Feeds data shards to the incremental encoder one at a time and times
+    /// only what remains after the last one arrives.
+    TimeToParity,
+    /// Builds the Merkle commitment over the encoded shards.
+    Commit,
+    /// Checks one shard's inclusion proof against the commitment.
+    VerifyProof,
+}
+
+impl Operation {
+    pub const ALL: &'static [Operation] = &[
+        Operation::Encode,
+        Operation::Decode1Missing,
+        Operation::DecodeFMissing,
+        Operation::Decode2fMissing,
+        Operation::Reconstruct1Missing,
+        Operation::ReconstructFMissing,
+        Operation::Reconstruct2fMissing,
+        Operation::Repair1Missing,
+        Operation::RepairFMissing,
+        Operation::Repair2fMissing,
+        Operation::TimeToParity,
+        Operation::Commit,
+        Operation::VerifyProof,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Operation::Encode => "encode",
+            Operation::Decode1Missing => "decode_1_missing",
+            Operation::DecodeFMissing => "decode_f_missing",
+            Operation::Decode2fMissing => "decode_2f_missing",
+            Operation::Reconstruct1Missing => "reconstruct_1_missing",
+            Operation::ReconstructFMissing => "reconstruct_f_missing",
+            Operation::Reconstruct2fMissing => "reconstruct_2f_missing",
+            Operation::Repair1Missing => "repair_1_missing",
+            Operation::RepairFMissing => "repair_f_missing",
+            Operation::Repair2fMissing => "repair_2f_missing",
+            Operation::TimeToParity => "time_to_parity",
+            Operation::Commit => "commit",
+            Operation::VerifyProof => "verify_proof",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|op| op.name() == name)
+    }
+
+    /// Number of shards dropped before decoding.
+    pub fn missing_count(self, config: &BenchConfig) -> usize {
+        match self {
+            Operation::Encode
+            | Operation::TimeToParity
+            | Operation::Commit
+            | Operation::VerifyProof => 0,
+            Operation::Decode1Missing
+            | Operation::Reconstruct1Missing
+            | Operation::Repair1Missing => 1,
+            Operation::DecodeFMissing
+            | Operation::ReconstructFMissing
+            | Operation::RepairFMissing => config.f,
+            Operation::Decode2fMissing
+            | Operation::Reconstruct2fMissing
+            | Operation::Repair2fMissing => 2 * config.f,
+        }
+    }
+
+    /// Indices of the dropped shards: the leading ones, or the trailing parity for repairs.
+    pub fn missing_indices(self, config: &BenchConfig) -> Vec<usize> {
+        let count = self.missing_count(config);
+        match self {
+            Operation::Repair1Missing | Operation::RepairFMissing | Operation::Repair2fMissing => {
+                (config.total_shards() - count..config.total_shards()).collect()
+            }
+            _ => (0..count).collect(),
+        }
+    }
+
+    /// Whether the operation rebuilds parity shards rather than stopping at the payload.
+    pub fn is_full_reconstruction(self) -> bool {
+        matches!(
+            self,
+            Operation::Reconstruct1Missing
+                | Operation::ReconstructFMissing
+                | Operation::Reconstruct2fMissing
+        )
+    }
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Whether caches are left warm or evicted before each sample.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheMode {
+    /// Inputs stay cached across iterations, as in the divan benches.
+    Warm,
+    /// Caches are scrubbed before every sample; the scrub itself is not timed.
+    Cold,
+}
+
+impl CacheMode {
+    pub fn name(self) -> &'static str {
+        match self {
+            CacheMode::Warm => "warm",
+            CacheMode::Cold => "cold",
+        }
+    }
+}
+
+/// One timed run of an operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sample {
+    pub elapsed: Duration,
+    /// TSC ticks over the same span, when the CPU has a TSC.
+    pub cycles: Option<u64>,
+}
+
+/// Median TSC ticks of `samples`, if every one was counted.
+pub fn median_cycles(samples: &[Sample]) -> Option<u64> {
+    let mut cycles = samples
+        .iter()
+        .map(|sample| sample.cycles)
+        .collect::<Option<Vec<u64>>>()?;
+    cycles.sort_unstable();
+    cycles.get(cycles.len() / 2).copied()
+}
+
+/// Wall clock and TSC read together, where a timed span begins.
+#[derive(Clone, Copy)]
+struct Stamp {
+    instant: Instant,
+    ticks: Option<u64>,
+}
+
+impl Stamp {
+    fn now() -> Self {
+        let ticks = tsc::read();
+        Self {
+            instant: Instant::now(),
+            ticks,
+        }
+    }
+}
+
+/// Records individual timings of adapter operations.
+pub struct Sampler {
+    samples: usize,
+    warmup: usize,
+    scrubber: Option<Scrubber>,
+}
+
+impl Sampler {
+    /// Hot-cache sampling, matching the divan benches.
+    pub fn warm(samples: usize) -> Self {
+        Self {
+            samples,
+            warmup: WARMUP_ITERATIONS,
+            scrubber: None,
+        }
+    }
+
+    /// Cold-cache sampling: `scrubber` evicts the caches before every sample.
+    pub fn cold(samples: usize, scrubber: Scrubber) -> Self {
+        Self {
+            samples,
+            warmup: WARMUP_ITERATIONS,
+            scrubber: Some(scrubber),
+        }
+    }
+
+    /// Overrides the warmup count, for operations that take seconds per run.
+    pub fn with_warmup(mut self, iterations: usize) -> Self {
+        self.warmup = iterations;
+        self
+    }
+
+    pub fn cache_mode(&self) -> CacheMode {
+        match self.scrubber {
+            Some(_) => CacheMode::Cold,
+            None => CacheMode::Warm,
+        }
+    }
+
+    /// Times individual runs of `operation` on `data`, with their TSC ticks.
+    pub fn measure(
+        &mut self,
+        codec: &dyn ErasureCodec,
+        config: &BenchConfig,
+        operation: Operation,
+        data: &[u8],
+    ) -> codec::Result<Vec<Sample>> {
+        let (k, m) = (config.data_shards(), config.coding_shards());
+
+        // Each run returns when its timed span began, if later than the call
+        let mut run: Box<dyn FnMut() -> codec::Result<Option<Stamp>>> = match operation {
+            Operation::Encode => Box::new(|| {
+                black_box(codec.encode(k, m, data)?);
+                Ok(None)
+            }),
+            Operation::TimeToParity => {
+                let shards = split_shards(data, k, aligned_shard_size(data.len(), k));
+                Box::new(move || {
+                    let mut last_arrival = None;
+                    let mut arrivals = shards.iter().cloned().enumerate().map(|(i, shard)| {
+                        if i + 1 == k {
+                            last_arrival = Some(Stamp::now());
+                        }
+                        shard
+                    });
+                    black_box(codec.encode_incremental(k, m, &mut arrivals)?);
+                    Ok(last_arrival)
+                })
+            }
+            Operation::Commit => {
+                let shards = codec.encode(k, m, data)?;
+                Box::new(move || {
+                    black_box(MerkleTree::new(&shards).root());
+                    Ok(None)
+                })
+            }
+            Operation::VerifyProof => {
+                let shards = codec.encode(k, m, data)?;
+                let tree = MerkleTree::new(&shards);
+                let (root, proof) = (tree.root(), tree.proof(0).expect("at least one shard"));
+                Box::new(move || {
+                    if !black_box(proof.verify(&root, shards.len(), &shards[0])) {
+                        return Err("inclusion proof rejected".into());
+                    }
+                    Ok(None)
+                })
+            }
+            _ => {
+                let missing = operation.missing_indices(config);
+                let received: Vec<Option<Vec<u8>>> = codec
+                    .encode(k, m, data)?
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, shard)| (!missing.contains(&i)).then_some(shard))
+                    .collect();
+                match operation {
+                    Operation::Repair1Missing
+                    | Operation::RepairFMissing
+                    | Operation::Repair2fMissing => Box::new(move || {
+                        black_box(codec.repair(k, m, &received, &missing)?);
+                        Ok(None)
+                    }),
+                    _ if operation.is_full_reconstruction() => Box::new(move || {
+                        black_box(codec.reconstruct(k, m, &received)?);
+                        Ok(None)
+                    }),
+                    _ => Box::new(move || {
+                        black_box(codec.decode(k, m, &received, data.len())?);
+                        Ok(None)
+                    }),
+                }
+            }
+        };
+
+        for _ in 0..self.warmup {
+            run()?;
+        }
+        let mut samples = Vec::with_capacity(self.samples);
+        for _ in 0..self.samples {
+            if let Some(scrubber) = &mut self.scrubber {
+                scrubber.scrub();
+            }
+            let start = Stamp::now();
+            let span_start = run()?.unwrap_or(start);
+            let (elapsed, ticks) = (span_start.instant.elapsed(), tsc::read());
+            samples.push(Sample {
+                elapsed,
+                cycles: ticks
+                    .zip(span_start.ticks)
+                    .map(|(end, start)| end.saturating_sub(start)),
+            });
+        }
+        Ok(samples)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_one_timing_per_sample() {
+        let config = BenchConfig::new(2, 4096);
+        let data = vec![7u8; config.data_size];
+        let mut samplers = [
+            Sampler::warm(5),
+            Sampler::cold(5, Scrubber::with_size(1 << 20)),
+        ];
+        for codec in codec::backends() {
+            for &operation in Operation::ALL {
+                for sampler in &mut samplers {
+                    let timings = sampler
+                        .measure(codec.as_ref(), &config, operation, &data)
+                        .unwrap();
+                    assert_eq!(timings.len(), 5, "{} {}", codec.name(), operation);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn samples_count_cycles_with_a_tsc() {
+        let config = BenchConfig::new(2, 4096);
+        let data = vec![7u8; config.data_size];
+        for codec in codec::backends() {
+            let samples = Sampler::warm(5)
+                .measure(codec.as_ref(), &config, Operation::Encode, &data)
+                .unwrap();
+            assert_eq!(samples.len(), 5, "{}", codec.name());
+            assert_eq!(
+                median_cycles(&samples).is_some(),
+                cfg!(target_arch = "x86_64"),
+                "{}",
+                codec.name()
+            );
+        }
+    }
+
+    #[test]
+    fn operation_names_round_trip() {
+        for &operation in Operation::ALL {
+            assert_eq!(Operation::from_name(operation.name()), Some(operation));
+        }
+    }
+}
@@ -0,0 +1,23 @@
+//! Reed-Solomon benchmark suite: shared configuration and backend adapters.
+
+pub mod affinity;
+pub mod arrival;
+pub mod broadcast;
+pub mod cache;
+pub mod codec;
+pub mod commitment;
+pub mod compat;
+pub mod config;
+pub mod container;
+pub mod data;
+pub mod fuzz;
+pub mod golden;
+pub mod harness;
+pub mod misuse;
+pub mod network;
+pub mod report;
+pub mod rng;
+pub mod sampling;
+pub mod square;
+pub mod stats;
+pub mod tsc;
@@ -1,10 +1,829 @@
+use reed_solomon_benchmark::affinity::Placement;
+use reed_solomon_benchmark::arrival::{self, DelayModel};
+use reed_solomon_benchmark::broadcast::{self, Fault, Scenario, Sender};
+use reed_solomon_benchmark::cache::{self, Scrubber};
+use reed_solomon_benchmark::codec::{self, ErasureCodec};
+use reed_solomon_benchmark::compat;
+use reed_solomon_benchmark::config::{
+    self, BenchConfig, DATA_SIZES, F_VALUES, LARGE_DATA_SIZES, UNALIGNED_DATA_SIZES,
+};
+use reed_solomon_benchmark::data::{Content, DEFAULT_SEED};
+use reed_solomon_benchmark::fuzz;
+use reed_solomon_benchmark::golden;
+use reed_solomon_benchmark::harness::{self, Operation, Sample, Sampler};
+use reed_solomon_benchmark::misuse;
+use reed_solomon_benchmark::network::{self, Loopback, Transport};
+use reed_solomon_benchmark::report::{
+    self, ArrivalRow, ArrivalTimings, BroadcastRow, BroadcastTimings, CacheRow, LargeRow,
+    LargeTimings, LatencyRow, NetworkRow, NetworkTimings, SamplingRow,
+};
+use reed_solomon_benchmark::rng::Rng;
+use reed_solomon_benchmark::sampling;
+use reed_solomon_benchmark::stats::Distribution;
+use reed_solomon_benchmark::tsc;
+use std::collections::HashMap;
+use std::process::exit;
+
+/// Samples recorded per configuration by `latency` unless `--samples` is given.
+const DEFAULT_SAMPLES: usize = 1000;
+
+/// Samples per configuration for `coldcache`; every sample streams the scrub buffer.
+const DEFAULT_COLD_SAMPLES: usize = 100;
+
+/// Arrival orders replayed per configuration by `arrival`.
+const DEFAULT_ARRIVAL_SAMPLES: usize = 200;
+
+/// Seeded runs per configuration for `broadcast`.
+const DEFAULT_BROADCAST_RUNS: usize = 10;
+
+/// Light clients simulated per configuration by `sampling`.
+const DEFAULT_CLIENTS: usize = 1000;
+
+/// Shards each client samples, one detection rate per count.
+const DEFAULT_DRAWS: &[usize] = &[1, 2, 4, 8, 16, 32];
+
+/// Fresh cases per backend for `fuzz`.
+const DEFAULT_FUZZ_CASES: usize = 1000;
+
+/// Where `fuzz` appends failing cases unless `--corpus` is given.
+const DEFAULT_CORPUS: &str = "fuzz/corpus.txt";
+
+/// Disseminations per configuration for `network`.
+const DEFAULT_NETWORK_SAMPLES: usize = 50;
+
+/// Receiver threads `network` spreads the shards over.
+const DEFAULT_RECEIVERS: usize = 8;
+
+/// Samples per configuration for `large`; galois_16 takes seconds per encode at F=500.
+const DEFAULT_LARGE_SAMPLES: usize = 5;
+
 fn main() {
-    println!("Reed-Solomon Benchmark Suite");
-    println!("Run benchmarks with: cargo bench");
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let options = parse_options(args.get(1..).unwrap_or_default());
+
+    match args.first().map(String::as_str) {
+        Some("backends") => {
+            // Only the backends enabled via cargo features are listed
+            for codec in codec::backends() {
+                println!("{}", codec.name());
+            }
+        }
+        Some("capabilities") => print!("{}", report::render_capabilities(&codec::backends())),
+        Some("latency") => latency(&options),
+        Some("coldcache") => cold_cache(&options),
+        Some("large") => large(&options),
+        Some("arrival") => arrivals(&options),
+        Some("network") => network(&options),
+        Some("broadcast") => broadcasts(&options),
+        Some("sampling") => availability_sampling(&options),
+        Some("compat") => compatibility(&options),
+        Some("golden") => golden_vectors(&options),
+        Some("fuzz") => fuzz_decoders(&options),
+        Some("errors") => error_catalogue(&options),
+        _ => {
+            println!("Reed-Solomon Benchmark Suite");
+            println!("Run benchmarks with: cargo bench");
+            println!("List compiled-in backends with: cargo run -- backends");
+            println!("Show each backend's shard limits with: cargo run -- capabilities");
+            println!(
+                "Latency percentiles: cargo run --release -- latency \\
+                 [--backend NAME,..] [--op OP,..] [--content KIND,..] [--f F,..] [--sizes SET] [--seed N] \\
+                 [--samples N] [--pin CORE] [--nice N] [--out FILE]"
+            );
+            println!(
+                "Warm vs cold cache: cargo run --release -- coldcache \\
+                 [--backend NAME,..] [--op OP,..] [--content KIND,..] [--f F,..] [--sizes SET] [--seed N] \\
+                 [--samples N] [--scrub-mb MB] [--pin CORE] [--nice N] [--out FILE]"
+            );
+            println!(
+                "Large committees: cargo run --release -- large \\
+                 [--backend NAME,..] [--f F,..] [--samples N] [--pin CORE] [--nice N] [--out FILE]"
+            );
+            println!(
+                "Decode on k-th arrival: cargo run --release -- arrival \\
+                 [--backend NAME,..] [--f F,..] [--sizes SET] [--seed N] [--samples N] \\
+                 [--delay-us US] [--jitter-us US] [--pin CORE] [--nice N] [--out FILE]"
+            );
+            println!(
+                "Loopback dissemination: cargo run --release -- network \\
+                 [--backend NAME,..] [--f F,..] [--sizes SET] [--transport tcp|unix] \\
+                 [--receivers N] [--seed N] [--samples N] [--pin CORE] [--nice N] [--out FILE]"
+            );
+            println!(
+                "Byzantine reliable broadcast: cargo run --release -- broadcast \\
+                 [--backend NAME,..] [--f F,..] [--sizes SET] [--fault drop|delay|corrupt|mixed|collude] \\
+                 [--faulty N] [--sender honest|equivocate] [--extra-nodes N] [--seed N] [--samples N] [--delay-us US] [--jitter-us US] \\
+                 [--pin CORE] [--nice N] [--out FILE]"
+            );
+            println!(
+                "Data-availability sampling: cargo run --release -- sampling \\
+                 [--backend NAME,..] [--f F,..] [--sizes SET] [--withhold N] [--clients N] \\
+                 [--draws N,..] [--seed N] [--out FILE]"
+            );
+            println!(
+                "Cross-backend compatibility: cargo run --release -- compat \\
+                 [--backend NAME,..] [--f F,..] [--seed N] [--out FILE]"
+            );
+            println!(
+                "Golden vectors: cargo run -- golden [--backend NAME,..] \\
+                 [--vectors FILE | --regenerate FILE] [--out FILE]"
+            );
+            println!(
+                "Malformed-input fuzzing: cargo run --release -- fuzz \\
+                 [--backend NAME,..] [--cases N] [--seed N] [--corpus FILE] [--out FILE]"
+            );
+            println!("Error catalogue: cargo run -- errors [--backend NAME,..] [--regenerate FILE] [--out FILE]");
+        }
+    }
+}
+
+/// Records every sample per configuration and reports percentiles and histograms.
+fn latency(options: &HashMap<String, String>) {
+    let placement = placement(options);
+    let mut sampler = Sampler::warm(sample_count(options, DEFAULT_SAMPLES));
+    let calibration = calibration();
+
+    let seed = seed(options);
+
+    let mut rows = Vec::new();
+    for codec in selected_backends(options) {
+        for &operation in &selected_operations(options) {
+            for content in selected_contents(options) {
+                for config in selected_configs(options) {
+                    let samples = supported(codec.as_ref(), &config).then(|| {
+                        let data = payload(&content, config.data_size, seed);
+                        sampler
+                            .measure(codec.as_ref(), &config, operation, &data)
+                            .unwrap_or_else(|err| fail(&format!("{}: {}", codec.name(), err)))
+                    });
+                    rows.push(LatencyRow {
+                        backend: codec.label(),
+                        operation,
+                        content: content.name(),
+                        config,
+                        cycles: samples.as_deref().and_then(harness::median_cycles),
+                        distribution: samples.as_deref().map(distribution),
+                    });
+                }
+            }
+        }
+    }
+
+    emit(
+        options,
+        report::render_placement(&placement) + &report::render_latency(&rows, calibration.as_ref()),
+    );
+}
+
+/// Times every benchmark with hot caches and with caches evicted before each sample.
+fn cold_cache(options: &HashMap<String, String>) {
+    let placement = placement(options);
+    let samples = sample_count(options, DEFAULT_COLD_SAMPLES);
+    let scrubber = match options.get("scrub-mb") {
+        Some(mb) => Scrubber::with_size(
+            mb.parse::<usize>()
+                .unwrap_or_else(|_| fail("--scrub-mb expects a number"))
+                * 1024
+                * 1024,
+        ),
+        None => Scrubber::for_llc(),
+    };
+    eprintln!(
+        "last-level cache {}MB, scrubbing {}MB before each cold sample",
+        cache::llc_size() >> 20,
+        scrubber.size() >> 20
+    );
+    let mut warm = Sampler::warm(samples);
+    let mut cold = Sampler::cold(samples, scrubber);
+    let calibration = calibration();
+
+    let seed = seed(options);
+
+    let mut rows = Vec::new();
+    for codec in selected_backends(options) {
+        for &operation in &selected_operations(options) {
+            for content in selected_contents(options) {
+                for config in selected_configs(options) {
+                    let samples = supported(codec.as_ref(), &config).then(|| {
+                        let data = payload(&content, config.data_size, seed);
+                        [&mut warm, &mut cold].map(|sampler| {
+                            sampler
+                                .measure(codec.as_ref(), &config, operation, &data)
+                                .unwrap_or_else(|err| fail(&format!("{}: {}", codec.name(), err)))
+                        })
+                    });
+                    let cycles = samples.as_ref().map_or((None, None), |[warm, cold]| {
+                        (harness::median_cycles(warm), harness::median_cycles(cold))
+                    });
+                    rows.push(CacheRow {
+                        backend: codec.label(),
+                        operation,
+                        content: content.name(),
+                        config,
+                        timings: samples
+                            .as_ref()
+                            .map(|[warm, cold]| (distribution(warm), distribution(cold))),
+                        cycles,
+                    });
+                }
+            }
+        }
+    }
+
+    emit(
+        options,
+        report::render_placement(&placement)
+            + &report::render_cache_comparison(&rows, calibration.as_ref()),
+    );
+}
+
+/// Encode and decode_f_missing of the large-committee tier, with per-node shard sizes.
+fn large(options: &HashMap<String, String>) {
+    let placement = placement(options);
+    // One warmup run: the per-run cost dwarfs any allocator or predictor settling
+    let mut sampler = Sampler::warm(sample_count(options, DEFAULT_LARGE_SAMPLES)).with_warmup(1);
+    let calibration = calibration();
+
+    let configs = match options.get("f") {
+        Some(_) => config::configs(&selected_f_values(options), LARGE_DATA_SIZES),
+        None => config::large_configs(),
+    };
+    let seed = seed(options);
+
+    let mut rows = Vec::new();
+    for codec in selected_backends(options) {
+        for &config in &configs {
+            let timings = supported(codec.as_ref(), &config).then(|| {
+                let data = payload(&Content::Ramp, config.data_size, seed);
+                let (k, m) = (config.data_shards(), config.coding_shards());
+                let shard_size = codec
+                    .encode(k, m, &data)
+                    .unwrap_or_else(|err| fail(&format!("{}: {}", codec.name(), err)))[0]
+                    .len();
+                let [encode, decode_f_missing] = [Operation::Encode, Operation::DecodeFMissing]
+                    .map(|operation| {
+                        sampler
+                            .measure(codec.as_ref(), &config, operation, &data)
+                            .unwrap_or_else(|err| fail(&format!("{}: {}", codec.name(), err)))
+                    });
+                LargeTimings {
+                    shard_size,
+                    encode: distribution(&encode),
+                    decode_f_missing: distribution(&decode_f_missing),
+                    cycles: (
+                        harness::median_cycles(&encode),
+                        harness::median_cycles(&decode_f_missing),
+                    ),
+                }
+            });
+            rows.push(LargeRow {
+                backend: codec.label(),
+                config,
+                timings,
+            });
+        }
+    }
+
+    emit(
+        options,
+        report::render_placement(&placement) + &report::render_large(&rows, calibration.as_ref()),
+    );
+}
+
+/// Replays seeded arrival orders and times decoding from the `k`-th arrival.
+fn arrivals(options: &HashMap<String, String>) {
+    let placement = placement(options);
+    let trials = sample_count(options, DEFAULT_ARRIVAL_SAMPLES);
+    let model = delay_model(options);
+    let seed = seed(options);
+
+    let mut rows = Vec::new();
+    for codec in selected_backends(options) {
+        for config in selected_configs(options) {
+            let timings = supported(codec.as_ref(), &config).then(|| {
+                let data = payload(&Content::Ramp, config.data_size, seed);
+                let replays =
+                    arrival::simulate(codec.as_ref(), &config, &data, &model, trials, seed)
+                        .unwrap_or_else(|err| fail(&format!("{}: {}", codec.name(), err)));
+                let missing: usize = replays
+                    .iter()
+                    .map(|replay| replay.missing_data_shards)
+                    .sum();
+                ArrivalTimings {
+                    kth_arrival: Distribution::new(
+                        replays.iter().map(|replay| replay.kth_arrival).collect(),
+                    ),
+                    decode: Distribution::new(replays.iter().map(|replay| replay.decode).collect()),
+                    completion: Distribution::new(
+                        replays.iter().map(|replay| replay.completion()).collect(),
+                    ),
+                    mean_missing_data: missing as f64 / replays.len().max(1) as f64,
+                }
+            });
+            rows.push(ArrivalRow {
+                backend: codec.label(),
+                config,
+                timings,
+            });
+        }
+    }
+
+    emit(
+        options,
+        report::render_placement(&placement) + &report::render_arrivals(&rows),
+    );
+}
+
+/// Sends shards to receiver threads over loopback sockets and times encode to decoded payload.
+fn network(options: &HashMap<String, String>) {
+    let samples = sample_count(options, DEFAULT_NETWORK_SAMPLES);
+    let transport = match options.get("transport") {
+        Some(name) => Transport::from_name(name)
+            .unwrap_or_else(|| fail(&format!("unknown transport: {}", name))),
+        None => Transport::Tcp,
+    };
+    let receivers = match options.get("receivers") {
+        Some(n) => n
+            .parse()
+            .ok()
+            .filter(|&n| n > 0)
+            .unwrap_or_else(|| fail("--receivers expects a positive number")),
+        None => DEFAULT_RECEIVERS,
+    };
+    let mut loopback = Loopback::new(transport, receivers)
+        .unwrap_or_else(|err| fail(&format!("{}: {}", transport, err)));
+    // After the receivers are spawned, so only this thread and each run's sender inherit the pin
+    let placement = placement(options);
+    let seed = seed(options);
+
+    let mut rows = Vec::new();
+    for codec in selected_backends(options) {
+        for config in selected_configs(options) {
+            let timings = supported(codec.as_ref(), &config).then(|| {
+                let data = payload(&Content::Ramp, config.data_size, seed);
+                let (k, m) = (config.data_shards(), config.coding_shards());
+                let shard_size = codec
+                    .encode(k, m, &data)
+                    .unwrap_or_else(|err| fail(&format!("{}: {}", codec.name(), err)))[0]
+                    .len();
+                let latency = loopback
+                    .measure(codec.as_ref(), &config, &data, samples)
+                    .unwrap_or_else(|err| fail(&format!("{}: {}", codec.name(), err)));
+                NetworkTimings {
+                    latency: Distribution::new(latency),
+                    wire_bytes: network::wire_bytes(k + m, shard_size),
+                }
+            });
+            rows.push(NetworkRow {
+                backend: codec.label(),
+                transport: transport.name(),
+                config,
+                timings,
+            });
+        }
+    }
+
+    emit(
+        options,
+        report::render_placement(&placement) + &report::render_network(&rows),
+    );
+}
+
+/// Simulates seeded reliable broadcasts with faulty nodes and checks safety and liveness.
+fn broadcasts(options: &HashMap<String, String>) {
+    let placement = placement(options);
+    let runs = sample_count(options, DEFAULT_BROADCAST_RUNS);
+    let fault = match options.get("fault") {
+        Some(name) => {
+            Fault::from_name(name).unwrap_or_else(|| fail(&format!("unknown fault: {}", name)))
+        }
+        None => Fault::Mixed,
+    };
+    let sender = match options.get("sender") {
+        Some(name) => {
+            Sender::from_name(name).unwrap_or_else(|| fail(&format!("unknown sender: {}", name)))
+        }
+        None => Sender::Honest,
+    };
+    let faulty = options.get("faulty").map(|n| {
+        n.parse::<usize>()
+            .unwrap_or_else(|_| fail("--faulty expects a number"))
+    });
+    let extra_nodes = match options.get("extra-nodes") {
+        Some(n) => n
+            .parse::<usize>()
+            .unwrap_or_else(|_| fail("--extra-nodes expects a number")),
+        // n = 3F only holds up against an honest sender
+        None => match sender {
+            Sender::Honest => 0,
+            Sender::Equivocate => 1,
+        },
+    };
+    let delays = delay_model(options);
+    let seed = seed(options);
+
+    let mut rows = Vec::new();
+    for codec in selected_backends(options) {
+        for config in selected_configs(options) {
+            // Up to F faulty nodes unless told otherwise
+            let faulty = faulty.unwrap_or(config.f);
+            let nodes = config.total_shards() + extra_nodes;
+            let supported = codec.supports(config.data_shards(), nodes - config.data_shards());
+            let timings = supported.then(|| {
+                let data = payload(&Content::Ramp, config.data_size, seed);
+                let outcomes: Vec<_> = (0..runs as u64)
+                    .map(|run| {
+                        let scenario = Scenario {
+                            sender,
+                            extra_nodes,
+                            faulty,
+                            fault,
+                            delays,
+                            seed: seed.wrapping_add(run),
+                        };
+                        broadcast::simulate(codec.as_ref(), &config, &data, &scenario)
+                            .unwrap_or_else(|err| fail(&format!("{}: {}", codec.name(), err)))
+                    })
+                    .collect();
+                let share: f64 = outcomes.iter().map(|outcome| outcome.coding_share()).sum();
+                BroadcastTimings {
+                    runs,
+                    safe: outcomes.iter().filter(|outcome| outcome.is_safe()).count(),
+                    live: outcomes.iter().filter(|outcome| outcome.is_live()).count(),
+                    completion: Distribution::new(
+                        outcomes.iter().map(|outcome| outcome.completion).collect(),
+                    ),
+                    coding_share: share / runs.max(1) as f64,
+                    messages: outcomes.first().map_or(0, |outcome| outcome.messages),
+                }
+            });
+            rows.push(BroadcastRow {
+                backend: codec.label(),
+                sender: sender.name(),
+                fault: fault.name(),
+                faulty,
+                nodes,
+                config,
+                timings,
+            });
+        }
+    }
+
+    emit(
+        options,
+        report::render_placement(&placement) + &report::render_broadcast(&rows),
+    );
+}
+
+/// Withholds shards of an encoded block and measures how often sampling clients notice.
+fn availability_sampling(options: &HashMap<String, String>) {
+    let number = |key: &str| {
+        options.get(key).map(|n| {
+            n.parse::<usize>()
+                .unwrap_or_else(|_| fail(&format!("--{} expects a number", key)))
+        })
+    };
+    let withhold = number("withhold");
+    let clients = number("clients").unwrap_or(DEFAULT_CLIENTS);
+    let draws = match options.get("draws") {
+        Some(list) => list
+            .split(',')
+            .map(|n| {
+                n.parse()
+                    .unwrap_or_else(|_| fail("--draws expects a list of numbers"))
+            })
+            .collect(),
+        None => DEFAULT_DRAWS.to_vec(),
+    };
+    let seed = seed(options);
+
+    let mut rows = Vec::new();
+    for codec in selected_backends(options) {
+        for config in selected_configs(options) {
+            let sampling = supported(codec.as_ref(), &config).then(|| {
+                let data = payload(&Content::Ramp, config.data_size, seed);
+                // By default just enough to make the block undecodable
+                let count = withhold.unwrap_or_else(|| sampling::minimal_withholding(&config));
+                if count > config.total_shards() {
+                    fail(&format!(
+                        "--withhold {} exceeds the {} shards of F={}",
+                        count,
+                        config.total_shards(),
+                        config.f
+                    ));
+                }
+                let mut indices: Vec<usize> = (0..config.total_shards()).collect();
+                Rng::new(seed).shuffle(&mut indices);
+                indices.truncate(count);
+                sampling::simulate(
+                    codec.as_ref(),
+                    &config,
+                    &data,
+                    &indices,
+                    &draws,
+                    clients,
+                    seed,
+                )
+                .unwrap_or_else(|err| fail(&format!("{}: {}", codec.name(), err)))
+            });
+            rows.push(SamplingRow {
+                backend: codec.label(),
+                config,
+                sampling,
+            });
+        }
+    }
+
+    emit(options, report::render_sampling(&rows));
+}
+
+/// Checks systematic-ness and decodes every backend's shards with every other backend.
+fn compatibility(options: &HashMap<String, String>) {
+    let f_values = match options.get("f") {
+        Some(_) => selected_f_values(options),
+        None => F_VALUES.to_vec(),
+    };
+    // Payload sizes are chosen by `compat::check`, so any one size will do
+    let configs = config::configs(&f_values, &[0]);
+    let compat = compat::check(&selected_backends(options), &configs, seed(options))
+        .unwrap_or_else(|err| fail(&err.to_string()));
+    emit(options, report::render_compat(&compat));
+}
+
+/// Compares every backend against the golden vectors, or rewrites them with `--regenerate`.
+fn golden_vectors(options: &HashMap<String, String>) {
+    let codecs = selected_backends(options);
+    if let Some(path) = options.get("regenerate") {
+        // Vectors of backends not compiled in are carried over unchanged
+        let existing = match std::fs::read_to_string(path) {
+            Ok(text) => golden::parse(&text).unwrap_or_else(|err| fail(&err.to_string())),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(err) => fail(&format!("{}: {}", path, err)),
+        };
+        let vectors =
+            golden::regenerate(&codecs, &existing).unwrap_or_else(|err| fail(&err.to_string()));
+        std::fs::write(path, golden::render(&vectors))
+            .unwrap_or_else(|err| fail(&format!("{}: {}", path, err)));
+        println!("wrote {} vectors to {}", vectors.len(), path);
+        return;
+    }
+
+    let text = match options.get("vectors") {
+        Some(path) => {
+            std::fs::read_to_string(path).unwrap_or_else(|err| fail(&format!("{}: {}", path, err)))
+        }
+        None => golden::VECTORS.to_string(),
+    };
+    let vectors = golden::parse(&text).unwrap_or_else(|err| fail(&err.to_string()));
+    let checks = golden::check(&codecs, &vectors).unwrap_or_else(|err| fail(&err.to_string()));
+    emit(options, report::render_golden(&checks));
+    let failed = checks.iter().filter(|check| !check.passed()).count();
+    if failed > 0 {
+        fail(&format!(
+            "{} of {} codewords differ from the golden vectors",
+            failed,
+            checks.len()
+        ));
+    }
+}
+
+/// Replays the corpus, then throws fresh malformed inputs at every backend's decoders.
+fn fuzz_decoders(options: &HashMap<String, String>) {
+    let cases = match options.get("cases") {
+        Some(n) => n
+            .parse()
+            .unwrap_or_else(|_| fail("--cases expects a number")),
+        None => DEFAULT_FUZZ_CASES,
+    };
+    let corpus = options.get("corpus").map_or(DEFAULT_CORPUS, String::as_str);
+    let codecs = selected_backends(options);
+
+    let known = match std::fs::read_to_string(corpus) {
+        Ok(text) => text,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(err) => fail(&format!("{}: {}", corpus, err)),
+    };
+    let mut runs = fuzz::replay(&codecs, &known).unwrap_or_else(|err| fail(&err.to_string()));
+    runs.extend(
+        fuzz::explore(&codecs, seed(options), cases).unwrap_or_else(|err| fail(&err.to_string())),
+    );
+    emit(options, report::render_fuzz(&runs));
+
+    let recorded = fuzz::record(std::path::Path::new(corpus), &runs)
+        .unwrap_or_else(|err| fail(&format!("{}: {}", corpus, err)));
+    let failed = runs.iter().filter(|run| run.failed()).count();
+    if failed > 0 {
+        fail(&format!(
+            "{} of {} cases failed; {} new ones added to {}",
+            failed,
+            runs.len(),
+            recorded,
+            corpus
+        ));
+    }
+}
+
+/// Tabulates which error kind each backend returns for each misuse.
+fn error_catalogue(options: &HashMap<String, String>) {
+    let entries =
+        misuse::catalogue(&selected_backends(options)).unwrap_or_else(|err| fail(&err.to_string()));
+    if let Some(path) = options.get("regenerate") {
+        std::fs::write(path, misuse::render(&entries))
+            .unwrap_or_else(|err| fail(&format!("{}: {}", path, err)));
+        println!("wrote {} entries to {}", entries.len(), path);
+        return;
+    }
+    emit(options, report::render_catalogue(&entries));
+}
+
+/// Whether `codec` can run `config`; unsupported configs become "unsupported" rows.
+fn supported(codec: &dyn ErasureCodec, config: &BenchConfig) -> bool {
+    codec.supports(config.data_shards(), config.coding_shards())
+}
+
+/// Per-message delay from `--delay-us` and `--jitter-us`, defaulting to [`DelayModel::default`].
+fn delay_model(options: &HashMap<String, String>) -> DelayModel {
+    let defaults = DelayModel::default();
+    let micros = |key: &str, default: std::time::Duration| match options.get(key) {
+        Some(us) => std::time::Duration::from_micros(
+            us.parse()
+                .unwrap_or_else(|_| fail(&format!("--{} expects a number", key))),
+        ),
+        None => default,
+    };
+    DelayModel {
+        base: micros("delay-us", defaults.base),
+        jitter: micros("jitter-us", defaults.jitter),
+    }
+}
+
+/// Prints a report and also writes it to `--out` when given.
+fn emit(options: &HashMap<String, String>, rendered: String) {
+    print!("{}", rendered);
+    if let Some(path) = options.get("out") {
+        std::fs::write(path, rendered).unwrap_or_else(|err| fail(&format!("{}: {}", path, err)));
+    }
+}
+
+fn payload(content: &Content, size: usize, seed: u64) -> Vec<u8> {
+    content
+        .generate(size, seed)
+        .unwrap_or_else(|err| fail(&format!("{} content: {}", content.name(), err)))
+}
+
+fn seed(options: &HashMap<String, String>) -> u64 {
+    options
+        .get("seed")
+        .map(|n| {
+            n.parse()
+                .unwrap_or_else(|_| fail("--seed expects a number"))
+        })
+        .unwrap_or(DEFAULT_SEED)
+}
+
+fn sample_count(options: &HashMap<String, String>, default: usize) -> usize {
+    options
+        .get("samples")
+        .map(|n| {
+            // Every report needs at least one sample to take percentiles of
+            n.parse()
+                .ok()
+                .filter(|&n| n > 0)
+                .unwrap_or_else(|| fail("--samples expects a positive number"))
+        })
+        .unwrap_or(default)
+}
+
+/// Calibrates the TSC, warning when its cycle counts may be off.
+fn calibration() -> Option<tsc::Calibration> {
+    let calibration = tsc::Calibration::measure();
+    for warning in calibration.iter().flat_map(tsc::Calibration::warnings) {
+        eprintln!("warning: {}", warning);
+    }
+    calibration
+}
+
+/// Wall-clock distribution of `samples`.
+fn distribution(samples: &[Sample]) -> Distribution {
+    Distribution::new(samples.iter().map(|sample| sample.elapsed).collect())
+}
+
+/// Pins the runner to `--pin CORE` and applies `--nice N`, warning about remaining noise.
+fn placement(options: &HashMap<String, String>) -> Placement {
+    let core = options.get("pin").map(|core| {
+        core.parse()
+            .unwrap_or_else(|_| fail("--pin expects a core number"))
+    });
+    let nice = options.get("nice").map(|nice| {
+        nice.parse()
+            .unwrap_or_else(|_| fail("--nice expects a number"))
+    });
+    let placement = Placement::apply(core, nice).unwrap_or_else(|err| fail(&err.to_string()));
+    for warning in placement.warnings() {
+        eprintln!("warning: {}", warning);
+    }
+    placement
+}
+
+/// Parses `--key value` pairs following the command.
+fn parse_options(args: &[String]) -> HashMap<String, String> {
+    let mut options = HashMap::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let Some(key) = arg.strip_prefix("--") else {
+            fail(&format!("unexpected argument `{}`", arg));
+        };
+        let value = args
+            .next()
+            .unwrap_or_else(|| fail(&format!("--{} expects a value", key)));
+        options.insert(key.to_string(), value.clone());
+    }
+    options
+}
+
+/// Backends named by `--backend`, or every compiled-in backend.
+fn selected_backends(options: &HashMap<String, String>) -> Vec<Box<dyn ErasureCodec>> {
+    match options.get("backend") {
+        Some(names) => names
+            .split(',')
+            .map(|name| {
+                codec::backend(name)
+                    .unwrap_or_else(|| fail(&format!("backend `{}` is not compiled in", name)))
+            })
+            .collect(),
+        None => codec::backends(),
+    }
+}
+
+/// Operations named by `--op`, or all of them.
+fn selected_operations(options: &HashMap<String, String>) -> Vec<Operation> {
+    match options.get("op") {
+        Some(names) => names
+            .split(',')
+            .map(|name| {
+                Operation::from_name(name)
+                    .unwrap_or_else(|| fail(&format!("unknown operation `{}`", name)))
+            })
+            .collect(),
+        None => Operation::ALL.to_vec(),
+    }
+}
+
+/// Configurations for `--f F,..` and `--sizes aligned|unaligned|all`.
+///
+/// Defaults to [`F_VALUES`] and the aligned [`DATA_SIZES`].
+fn selected_configs(options: &HashMap<String, String>) -> Vec<BenchConfig> {
+    let f_values = match options.get("f") {
+        Some(_) => selected_f_values(options),
+        None => F_VALUES.to_vec(),
+    };
+    let data_sizes = match options.get("sizes").map(String::as_str) {
+        None | Some("aligned") => DATA_SIZES.to_vec(),
+        Some("unaligned") => UNALIGNED_DATA_SIZES.to_vec(),
+        Some("all") => [DATA_SIZES, UNALIGNED_DATA_SIZES].concat(),
+        Some(other) => fail(&format!("unknown size set `{}`", other)),
+    };
+    config::configs(&f_values, &data_sizes)
+}
+
+/// F values listed by `--f F,..`; empty when the option is absent.
+fn selected_f_values(options: &HashMap<String, String>) -> Vec<usize> {
+    options
+        .get("f")
+        .map(|list| {
+            list.split(',')
+                .map(|f| match f.parse() {
+                    Ok(f) if f > 0 => f,
+                    _ => fail("--f expects positive numbers"),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Payload contents named by `--content`, or the divan benches' ramp.
+fn selected_contents(options: &HashMap<String, String>) -> Vec<Content> {
+    match options.get("content").map(String::as_str) {
+        Some("all") => Content::SYNTHETIC.to_vec(),
+        Some(names) => names
+            .split(',')
+            .map(|name| {
+                Content::from_name(name)
+                    .unwrap_or_else(|| fail(&format!("unknown content `{}`", name)))
+            })
+            .collect(),
+        None => vec![Content::Ramp],
+    }
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("error: {}", message);
+    exit(1)
 }
 
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "erasure")]
     #[test]
     fn test_reed_solomon_erasure() {
         use reed_solomon_erasure::galois_8::ReedSolomon;
@@ -18,6 +837,22 @@ mod tests {
         println!("reed-solomon-erasure encode works");
     }
 
+    #[cfg(feature = "erasure")]
+    #[test]
+    fn test_reed_solomon_erasure_galois_16() {
+        use reed_solomon_erasure::galois_16::ReedSolomon;
+
+        // GF(2^16) lifts galois_8's 256-shard cap
+        let encoder = ReedSolomon::new(200, 100).unwrap();
+
+        let mut shards: Vec<Vec<[u8; 2]>> = (0..300).map(|i| vec![[i as u8, 0]; 4]).collect();
+
+        encoder.encode(&mut shards).unwrap();
+        assert!(encoder.verify(&shards).unwrap());
+        println!("reed-solomon-erasure galois_16 encode works");
+    }
+
+    #[cfg(feature = "novelpoly")]
     #[test]
     fn test_reed_solomon_novelpoly() {
         use reed_solomon_novelpoly::{CodeParams, WrappedShard};
@@ -29,6 +864,7 @@ mod tests {
         println!("reed-solomon-novelpoly encode works");
     }
 
+    #[cfg(feature = "rs16")]
     #[test]
     fn test_reed_solomon_16() {
         use reed_solomon_16::ReedSolomonEncoder;
@@ -44,6 +880,7 @@ mod tests {
         println!("reed-solomon-16 encode works");
     }
 
+    #[cfg(feature = "simd")]
     #[test]
     fn test_reed_solomon_simd() {
         use reed_solomon_simd::ReedSolomonEncoder;
@@ -0,0 +1,417 @@
+//! Catalogue of what each backend returns for each way of misusing it.
+//!
+//! Every scenario is one fixed, deterministic call, built from a real F=2
+//! codeword. Most go through the adapters, whose own checks catch many
+//! misuses before the backend crate runs; the `native_*` ones call each crate
+//! directly, so its own error reaches the adapter's conversion. The
+//! catalogue records the [`ErrorKind`] and message each backend answers
+//! with, or that it accepted the call, so callers can see which failures
+//! they can tell apart on every backend. [`CATALOGUE`] pins it for review.
+
+use crate::codec::{self, DecoderSession, ErasureCodec, ErrorKind};
+use crate::config::BenchConfig;
+use std::panic::{self, AssertUnwindSafe};
+
+/// The checked-in catalogue of the default backends, one [`Entry::line`] each.
+pub const CATALOGUE: &str = include_str!("../golden/errors.txt");
+
+/// Codeword every scenario starts from: k = 2, m = 4.
+const CONFIG: BenchConfig = BenchConfig {
+    f: 2,
+    data_size: 1000,
+};
+
+/// One way of calling an adapter wrongly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Misuse {
+    DecodeTooFew,
+    DecodeShortList,
+    DecodeMixedLengths,
+    DecodeEmptyShards,
+    DecodeOddLength,
+    EncodeNoDataShards,
+    EncodeTooManyShards,
+    RepairOutOfRange,
+    UpdateOutOfRange,
+    UpdateLengthMismatch,
+    SessionConflict,
+    SessionTooFew,
+    IncrementalTooFew,
+    NativeTooFew,
+    NativeMixedLengths,
+    NativeNoDataShards,
+}
+
+impl Misuse {
+    pub const ALL: &'static [Misuse] = &[
+        Misuse::DecodeTooFew,
+        Misuse::DecodeShortList,
+        Misuse::DecodeMixedLengths,
+        Misuse::DecodeEmptyShards,
+        Misuse::DecodeOddLength,
+        Misuse::EncodeNoDataShards,
+        Misuse::EncodeTooManyShards,
+        Misuse::RepairOutOfRange,
+        Misuse::UpdateOutOfRange,
+        Misuse::UpdateLengthMismatch,
+        Misuse::SessionConflict,
+        Misuse::SessionTooFew,
+        Misuse::IncrementalTooFew,
+        Misuse::NativeTooFew,
+        Misuse::NativeMixedLengths,
+        Misuse::NativeNoDataShards,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Misuse::DecodeTooFew => "decode_too_few",
+            Misuse::DecodeShortList => "decode_short_list",
+            Misuse::DecodeMixedLengths => "decode_mixed_lengths",
+            Misuse::DecodeEmptyShards => "decode_empty_shards",
+            Misuse::DecodeOddLength => "decode_odd_length",
+            Misuse::EncodeNoDataShards => "encode_no_data_shards",
+            Misuse::EncodeTooManyShards => "encode_too_many_shards",
+            Misuse::RepairOutOfRange => "repair_out_of_range",
+            Misuse::UpdateOutOfRange => "update_out_of_range",
+            Misuse::UpdateLengthMismatch => "update_length_mismatch",
+            Misuse::SessionConflict => "session_conflict",
+            Misuse::SessionTooFew => "session_too_few",
+            Misuse::IncrementalTooFew => "incremental_too_few",
+            Misuse::NativeTooFew => "native_too_few",
+            Misuse::NativeMixedLengths => "native_mixed_lengths",
+            Misuse::NativeNoDataShards => "native_no_data_shards",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Misuse::DecodeTooFew => "decode with k - 1 shards present",
+            Misuse::DecodeShortList => "decode with one slot fewer than k + m",
+            Misuse::DecodeMixedLengths => "decode with one shard a byte short",
+            Misuse::DecodeEmptyShards => "decode with every shard empty",
+            Misuse::DecodeOddLength => "decode with every shard a byte short",
+            Misuse::EncodeNoDataShards => "encode with k = 0",
+            Misuse::EncodeTooManyShards => "encode 40000 + 40000 shards",
+            Misuse::RepairOutOfRange => "repair shard k + m",
+            Misuse::UpdateOutOfRange => "update_parity for data shard k",
+            Misuse::UpdateLengthMismatch => "update_parity with a longer new shard",
+            Misuse::SessionConflict => "session given two different shards 0",
+            Misuse::SessionTooFew => "session decoded after k - 1 shards",
+            Misuse::IncrementalTooFew => "encode_incremental fed k - 1 shards",
+            Misuse::NativeTooFew => "the crate itself decoding k - 1 shards",
+            Misuse::NativeMixedLengths => "the crate itself decoding one shard a byte short",
+            Misuse::NativeNoDataShards => "the crate itself decoding with k = 0",
+        }
+    }
+}
+
+/// What a backend did for one misuse.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Response {
+    Rejected { kind: ErrorKind, message: String },
+    Accepted,
+    Panicked,
+}
+
+impl Response {
+    /// The error kind, or what happened instead.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Response::Rejected { kind, .. } => kind.name(),
+            Response::Accepted => "accepted",
+            Response::Panicked => "panicked",
+        }
+    }
+}
+
+/// One backend's response to one misuse.
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub misuse: Misuse,
+    pub backend: &'static str,
+    pub response: Response,
+}
+
+impl Entry {
+    /// Misuse, backend, response label and any message, as in [`CATALOGUE`].
+    pub fn line(&self) -> String {
+        let line = format!(
+            "{} {} {}",
+            self.misuse.name(),
+            self.backend,
+            self.response.label()
+        );
+        match &self.response {
+            Response::Rejected { message, .. } => format!("{}: {}", line, message),
+            _ => line,
+        }
+    }
+}
+
+/// Renders `entries` in the format of [`CATALOGUE`].
+pub fn render(entries: &[Entry]) -> String {
+    entries.iter().map(|entry| entry.line() + "\n").collect()
+}
+
+/// The entries whose line differs from, or is missing in, the catalogue `text`.
+pub fn check<'a>(entries: &'a [Entry], text: &str) -> Vec<&'a Entry> {
+    entries
+        .iter()
+        .filter(|entry| !text.lines().any(|line| line == entry.line()))
+        .collect()
+}
+
+/// Every misuse against every backend in `codecs`, misuse by misuse.
+pub fn catalogue(codecs: &[Box<dyn ErasureCodec>]) -> codec::Result<Vec<Entry>> {
+    let data: Vec<u8> = (0..CONFIG.data_size).map(|i| (i % 251) as u8).collect();
+    let codewords = codecs
+        .iter()
+        .map(|codec| codec.encode(CONFIG.data_shards(), CONFIG.coding_shards(), &data))
+        .collect::<codec::Result<Vec<_>>>()?;
+
+    let mut entries = Vec::new();
+    for &misuse in Misuse::ALL {
+        for (codec, codeword) in codecs.iter().zip(&codewords) {
+            let outcome = panic::catch_unwind(AssertUnwindSafe(|| match misuse {
+                Misuse::NativeTooFew | Misuse::NativeMixedLengths | Misuse::NativeNoDataShards => {
+                    native(codec.name(), misuse, codeword)
+                }
+                _ => attempt(codec.as_ref(), misuse, codeword, &data),
+            }));
+            let response = match outcome {
+                Ok(Ok(())) => Response::Accepted,
+                Ok(Err(err)) => Response::Rejected {
+                    kind: err.kind(),
+                    message: err.to_string(),
+                },
+                Err(_) => Response::Panicked,
+            };
+            entries.push(Entry {
+                misuse,
+                backend: codec.name(),
+                response,
+            });
+        }
+    }
+    Ok(entries)
+}
+
+fn attempt(
+    codec: &dyn ErasureCodec,
+    misuse: Misuse,
+    codeword: &[Vec<u8>],
+    data: &[u8],
+) -> codec::Result<()> {
+    let (k, m) = (CONFIG.data_shards(), CONFIG.coding_shards());
+    let all: Vec<Option<Vec<u8>>> = codeword.iter().cloned().map(Some).collect();
+    let decode = |shards: Vec<Option<Vec<u8>>>| codec.decode(k, m, &shards, data.len()).map(drop);
+    match misuse {
+        Misuse::DecodeTooFew => {
+            let mut shards = all;
+            for shard in &mut shards[k - 1..] {
+                *shard = None;
+            }
+            decode(shards)
+        }
+        Misuse::DecodeShortList => decode(all[..k + m - 1].to_vec()),
+        Misuse::DecodeMixedLengths => {
+            let mut shards = all;
+            if let Some(shard) = &mut shards[0] {
+                shard.pop();
+            }
+            decode(shards)
+        }
+        Misuse::DecodeEmptyShards => decode(vec![Some(Vec::new()); k + m]),
+        Misuse::DecodeOddLength => {
+            let mut shards = all;
+            for shard in shards.iter_mut().flatten() {
+                shard.pop();
+            }
+            decode(shards)
+        }
+        Misuse::EncodeNoDataShards => codec.encode(0, m, data).map(drop),
+        Misuse::EncodeTooManyShards => codec.encode(40_000, 40_000, data).map(drop),
+        Misuse::RepairOutOfRange => codec.repair(k, m, &all, &[k + m]).map(drop),
+        Misuse::UpdateOutOfRange | Misuse::UpdateLengthMismatch => {
+            let mut parity = codeword[k..].to_vec();
+            let old = &codeword[0];
+            let (index, new) = match misuse {
+                Misuse::UpdateOutOfRange => (k, old.clone()),
+                _ => (0, vec![0; old.len() + 2]),
+            };
+            codec.update_parity(k, m, index, old, &new, &mut parity)
+        }
+        Misuse::SessionConflict | Misuse::SessionTooFew => {
+            let mut session = DecoderSession::new(codec, k, m, data.len());
+            for (index, shard) in codeword.iter().enumerate().take(k - 1) {
+                session.add_shard(index, shard.clone())?;
+            }
+            if misuse == Misuse::SessionConflict {
+                let mut conflicting = codeword[0].clone();
+                conflicting[0] ^= 1;
+                session.add_shard(0, conflicting)?;
+            }
+            session.decode().map(drop)
+        }
+        Misuse::IncrementalTooFew => {
+            let shard_len = codeword[0].len();
+            let mut arrivals = data.chunks(shard_len).take(k - 1).map(|chunk| {
+                let mut shard = chunk.to_vec();
+                shard.resize(shard_len, 0);
+                shard
+            });
+            codec.encode_incremental(k, m, &mut arrivals).map(drop)
+        }
+        _ => unreachable!("{} calls the crate directly", misuse.name()),
+    }
+}
+
+/// Runs a `native_*` misuse on the crate behind `backend`, converting its
+/// error as the adapter does.
+#[cfg(any(
+    feature = "erasure",
+    feature = "novelpoly",
+    feature = "rs16",
+    feature = "simd"
+))]
+fn native(backend: &str, misuse: Misuse, codeword: &[Vec<u8>]) -> codec::Result<()> {
+    let (k, m) = match misuse {
+        Misuse::NativeNoDataShards => (0, CONFIG.total_shards()),
+        _ => (CONFIG.data_shards(), CONFIG.coding_shards()),
+    };
+    let mut shards: Vec<Option<Vec<u8>>> = codeword.iter().cloned().map(Some).collect();
+    match misuse {
+        // Only the first k - 1 shards, all data shards, are left
+        Misuse::NativeTooFew => shards[CONFIG.data_shards() - 1..].fill(None),
+        Misuse::NativeMixedLengths => {
+            if let Some(shard) = &mut shards[0] {
+                shard.pop();
+            }
+        }
+        _ => {}
+    }
+    // The leopard crates take present shards by index, originals apart from recovery
+    #[cfg(any(feature = "rs16", feature = "simd"))]
+    let (original, recovery): (Vec<_>, Vec<_>) = shards
+        .iter()
+        .enumerate()
+        .filter_map(|(index, shard)| Some((index, shard.as_ref()?)))
+        .partition(|&(index, _)| index < k);
+    #[cfg(any(feature = "rs16", feature = "simd"))]
+    let recovery = recovery
+        .into_iter()
+        .map(|(index, shard)| (index - k, shard));
+
+    match backend {
+        #[cfg(feature = "erasure")]
+        "erasure" => {
+            let decoder = reed_solomon_erasure::galois_8::ReedSolomon::new(k, m)?;
+            Ok(decoder.reconstruct_data(&mut shards)?)
+        }
+        #[cfg(feature = "erasure")]
+        "erasure16" => {
+            let decoder = reed_solomon_erasure::galois_16::ReedSolomon::new(k, m)?;
+            let mut shards: Vec<Option<Vec<[u8; 2]>>> = shards
+                .iter()
+                .map(|shard| {
+                    let symbols = shard.as_ref()?.chunks_exact(2);
+                    Some(symbols.map(|s| [s[0], s[1]]).collect())
+                })
+                .collect();
+            Ok(decoder.reconstruct_data(&mut shards)?)
+        }
+        #[cfg(feature = "novelpoly")]
+        "novelpoly" => {
+            use reed_solomon_novelpoly::{CodeParams, WrappedShard};
+            let params = CodeParams::derive_parameters(k + m, k)?;
+            let shards = shards.into_iter().map(|shard| shard.map(WrappedShard::new));
+            params.make_encoder().reconstruct(shards.collect())?;
+            Ok(())
+        }
+        #[cfg(feature = "rs16")]
+        "rs16" => {
+            reed_solomon_16::decode(k, m, original, recovery)?;
+            Ok(())
+        }
+        #[cfg(feature = "simd")]
+        "simd" => {
+            reed_solomon_simd::decode(k, m, original, recovery)?;
+            Ok(())
+        }
+        other => Err(format!("no direct call into the crate behind {}", other).into()),
+    }
+}
+
+/// Without a backend compiled in there is no crate to call.
+#[cfg(not(any(
+    feature = "erasure",
+    feature = "novelpoly",
+    feature = "rs16",
+    feature = "simd"
+)))]
+fn native(backend: &str, _: Misuse, _: &[Vec<u8>]) -> codec::Result<()> {
+    Err(format!("no direct call into the crate behind {}", backend).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::backends;
+
+    #[test]
+    fn misuse_maps_to_the_same_kind_on_every_backend() {
+        let entries = catalogue(&backends()).unwrap();
+        let expect = |misuse: Misuse, kind: ErrorKind| {
+            for entry in entries.iter().filter(|entry| entry.misuse == misuse) {
+                assert_eq!(
+                    entry.response.label(),
+                    kind.name(),
+                    "{} {} {:?}",
+                    entry.backend,
+                    misuse.name(),
+                    entry.response
+                );
+            }
+        };
+        expect(Misuse::DecodeTooFew, ErrorKind::TooFewShards);
+        expect(Misuse::DecodeShortList, ErrorKind::SizeMismatch);
+        expect(Misuse::DecodeMixedLengths, ErrorKind::SizeMismatch);
+        expect(Misuse::DecodeEmptyShards, ErrorKind::SizeMismatch);
+        expect(Misuse::EncodeNoDataShards, ErrorKind::UnsupportedParams);
+        expect(Misuse::EncodeTooManyShards, ErrorKind::UnsupportedParams);
+        expect(Misuse::RepairOutOfRange, ErrorKind::InvalidIndex);
+        expect(Misuse::SessionConflict, ErrorKind::DuplicateIndex);
+        expect(Misuse::SessionTooFew, ErrorKind::TooFewShards);
+        expect(Misuse::IncrementalTooFew, ErrorKind::TooFewShards);
+        expect(Misuse::NativeTooFew, ErrorKind::TooFewShards);
+        expect(Misuse::NativeNoDataShards, ErrorKind::UnsupportedParams);
+        // novelpoly's own decoder does not compare shard lengths; its adapter does
+        for entry in entries
+            .iter()
+            .filter(|entry| entry.misuse == Misuse::NativeMixedLengths)
+        {
+            let expected = match entry.backend {
+                "novelpoly" => "accepted",
+                _ => ErrorKind::SizeMismatch.name(),
+            };
+            assert_eq!(entry.response.label(), expected, "{}", entry.backend);
+        }
+        assert!(entries
+            .iter()
+            .all(|entry| entry.response != Response::Panicked));
+    }
+
+    #[test]
+    fn catalogue_matches_the_checked_in_one() {
+        let entries = catalogue(&backends()).unwrap();
+        let changed: Vec<String> = check(&entries, CATALOGUE)
+            .iter()
+            .map(|entry| entry.line())
+            .collect();
+        assert!(
+            changed.is_empty(),
+            "changed entries:\n{}\nregenerate golden/errors.txt only if the change is intended",
+            changed.join("\n")
+        );
+    }
+}
@@ -0,0 +1,354 @@
+//! End-to-end dissemination over loopback sockets.
+//!
+//! The sender encodes a payload and writes each shard, framed as a
+//! [shard container](crate::container), to one of N receiver threads in
+//! turn. Receivers forward what they read to a collector, which decodes as
+//! soon as `k` shards are in. Everything runs on one machine, so the numbers
+//! price the kernel's socket path and framing, not a real network.
+
+use crate::codec::{self, DecoderSession, ErasureCodec};
+use crate::config::BenchConfig;
+use crate::container::{self, ShardHeader, HEADER_LEN};
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Unrecorded runs before sampling, while socket buffers grow to size.
+const WARMUP_RUNS: usize = 3;
+
+/// Socket family used between sender and receivers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transport {
+    Tcp,
+    /// Unix domain stream sockets; unavailable off Unix.
+    Unix,
+}
+
+impl Transport {
+    pub const ALL: &'static [Transport] = &[Transport::Tcp, Transport::Unix];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Transport::Tcp => "tcp",
+            Transport::Unix => "unix",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|t| t.name() == name)
+    }
+
+    /// A connected pair: the sender's writing end and the receiver's reading end.
+    fn connect(self, receiver: usize) -> io::Result<(Box<dyn Write + Send>, Box<dyn Read + Send>)> {
+        match self {
+            Transport::Tcp => {
+                let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+                let client = TcpStream::connect(listener.local_addr()?)?;
+                let (server, _) = listener.accept()?;
+                // Shards are written whole; don't let Nagle hold back the tail
+                client.set_nodelay(true)?;
+                Ok((Box::new(client), Box::new(server)))
+            }
+            #[cfg(unix)]
+            Transport::Unix => {
+                use std::os::unix::net::{UnixListener, UnixStream};
+
+                let path = std::env::temp_dir().join(format!(
+                    "rs-bench-{}-{}.sock",
+                    std::process::id(),
+                    receiver
+                ));
+                let _ = std::fs::remove_file(&path);
+                let listener = UnixListener::bind(&path)?;
+                let client = UnixStream::connect(&path);
+                let server = listener.accept();
+                std::fs::remove_file(&path)?;
+                Ok((Box::new(client?), Box::new(server?.0)))
+            }
+            #[cfg(not(unix))]
+            Transport::Unix => {
+                let _ = receiver;
+                Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "unix sockets need a Unix host",
+                ))
+            }
+        }
+    }
+}
+
+impl fmt::Display for Transport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+type Delivery = io::Result<(ShardHeader, Vec<u8>)>;
+
+/// A sender connected to N receiver threads, reused across runs.
+pub struct Loopback {
+    transport: Transport,
+    links: Vec<Box<dyn Write + Send>>,
+    deliveries: Receiver<Delivery>,
+    receivers: Vec<JoinHandle<()>>,
+    next_message: u64,
+}
+
+impl Loopback {
+    /// Connects `receivers` receiver threads over `transport`.
+    pub fn new(transport: Transport, receivers: usize) -> io::Result<Self> {
+        if receivers == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "at least one receiver is needed",
+            ));
+        }
+        let (deliver, deliveries) = mpsc::channel();
+        let mut links = Vec::with_capacity(receivers);
+        let mut threads = Vec::with_capacity(receivers);
+        for receiver in 0..receivers {
+            let (link, stream) = transport.connect(receiver)?;
+            links.push(link);
+            let deliver = deliver.clone();
+            threads.push(thread::spawn(move || receive(stream, deliver)));
+        }
+        Ok(Self {
+            transport,
+            links,
+            deliveries,
+            receivers: threads,
+            next_message: 0,
+        })
+    }
+
+    pub fn transport(&self) -> Transport {
+        self.transport
+    }
+
+    pub fn receivers(&self) -> usize {
+        self.links.len()
+    }
+
+    /// Encodes, sends and decodes `data` once; returns the time from the
+    /// start of encoding until the collector holds the payload.
+    pub fn disseminate(
+        &mut self,
+        codec: &dyn ErasureCodec,
+        config: &BenchConfig,
+        data: &[u8],
+    ) -> codec::Result<Duration> {
+        let (k, m) = (config.data_shards(), config.coding_shards());
+        let message = self.next_message;
+        self.next_message += 1;
+
+        let start = Instant::now();
+        let shards = codec.encode(k, m, data)?;
+        let header = ShardHeader {
+            message,
+            data_shards: k as u32,
+            parity_shards: m as u32,
+            index: 0,
+            shard_len: 0,
+            data_len: data.len() as u64,
+        };
+
+        let links = &mut self.links;
+        let deliveries = &self.deliveries;
+        thread::scope(|scope| {
+            let sender = scope.spawn(move || -> io::Result<()> {
+                let receivers = links.len();
+                for (index, shard) in shards.iter().enumerate() {
+                    let header = ShardHeader {
+                        index: index as u32,
+                        ..header
+                    };
+                    container::write_shard(&mut links[index % receivers], &header, shard)?;
+                }
+                links.iter_mut().try_for_each(|link| link.flush())
+            });
+
+            let mut session = DecoderSession::new(codec, k, m, data.len());
+            let mut latency = None;
+            let mut failure = None;
+            // Every shard is drained, even after a failure, so none is left over
+            // for the next run; a broken link ends the loopback, so it returns at once
+            for _ in 0..k + m {
+                let (header, shard) = deliveries.recv().map_err(|_| "every receiver hung up")??;
+                if failure.is_some() {
+                    continue;
+                }
+                let collect = || -> codec::Result<()> {
+                    if header.message != message {
+                        return Err(format!(
+                            "shard of message {} during {}",
+                            header.message, message
+                        )
+                        .into());
+                    }
+                    if session.add_shard(header.index as usize, shard)? && latency.is_none() {
+                        let payload = session.decode()?;
+                        latency = Some(start.elapsed());
+                        if payload != data {
+                            return Err("decoded payload differs from the one sent".into());
+                        }
+                    }
+                    Ok(())
+                };
+                failure = collect().err();
+            }
+            sender.join().expect("sender thread panicked")?;
+            match failure {
+                Some(err) => Err(err),
+                None => latency.ok_or_else(|| "fewer than k shards arrived".into()),
+            }
+        })
+    }
+
+    /// Times `samples` disseminations of `data`.
+    pub fn measure(
+        &mut self,
+        codec: &dyn ErasureCodec,
+        config: &BenchConfig,
+        data: &[u8],
+        samples: usize,
+    ) -> codec::Result<Vec<Duration>> {
+        for _ in 0..WARMUP_RUNS {
+            self.disseminate(codec, config, data)?;
+        }
+        (0..samples)
+            .map(|_| self.disseminate(codec, config, data))
+            .collect()
+    }
+}
+
+impl Drop for Loopback {
+    fn drop(&mut self) {
+        // Closing the sending ends lets every receiver see end of stream
+        self.links.clear();
+        for receiver in self.receivers.drain(..) {
+            let _ = receiver.join();
+        }
+    }
+}
+
+/// Bytes on the wire for one dissemination: every shard plus its container header.
+pub fn wire_bytes(shards: usize, shard_len: usize) -> usize {
+    shards * (HEADER_LEN + shard_len)
+}
+
+/// Receiver thread: forwards each container to the collector until the stream closes.
+fn receive(mut stream: Box<dyn Read + Send>, deliver: Sender<Delivery>) {
+    loop {
+        let delivery = match container::read_shard(&mut stream) {
+            Ok(Some(shard)) => Ok(shard),
+            Ok(None) => return,
+            Err(err) => Err(err),
+        };
+        let failed = delivery.is_err();
+        if deliver.send(delivery).is_err() || failed {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disseminates_over_every_transport() {
+        let config = BenchConfig::new(2, 8192);
+        let data: Vec<u8> = (0..config.data_size).map(|i| (i % 241) as u8).collect();
+        for &transport in Transport::ALL {
+            if cfg!(not(unix)) && transport == Transport::Unix {
+                continue;
+            }
+            let mut loopback = Loopback::new(transport, 3).unwrap();
+            for codec in codec::backends() {
+                let timings = loopback.measure(codec.as_ref(), &config, &data, 2).unwrap();
+                assert_eq!(timings.len(), 2, "{} {}", transport, codec.name());
+            }
+        }
+    }
+
+    /// Decodes with the wrapped backend, then flips a byte of the payload.
+    struct Corrupting(Box<dyn ErasureCodec>);
+
+    impl ErasureCodec for Corrupting {
+        fn name(&self) -> &'static str {
+            self.0.name()
+        }
+
+        fn capabilities(&self) -> codec::Capabilities {
+            self.0.capabilities()
+        }
+
+        fn encode(&self, k: usize, m: usize, data: &[u8]) -> codec::Result<Vec<Vec<u8>>> {
+            self.0.encode(k, m, data)
+        }
+
+        fn decode(
+            &self,
+            k: usize,
+            m: usize,
+            shards: &[Option<Vec<u8>>],
+            data_len: usize,
+        ) -> codec::Result<Vec<u8>> {
+            let mut payload = self.0.decode(k, m, shards, data_len)?;
+            payload[0] ^= 1;
+            Ok(payload)
+        }
+
+        fn reconstruct(
+            &self,
+            k: usize,
+            m: usize,
+            shards: &[Option<Vec<u8>>],
+        ) -> codec::Result<Vec<Vec<u8>>> {
+            self.0.reconstruct(k, m, shards)
+        }
+
+        fn update_parity(
+            &self,
+            k: usize,
+            m: usize,
+            index: usize,
+            old: &[u8],
+            new: &[u8],
+            parity: &mut [Vec<u8>],
+        ) -> codec::Result<()> {
+            self.0.update_parity(k, m, index, old, new, parity)
+        }
+
+        fn encode_incremental(
+            &self,
+            k: usize,
+            m: usize,
+            arrivals: &mut dyn Iterator<Item = Vec<u8>>,
+        ) -> codec::Result<Vec<Vec<u8>>> {
+            self.0.encode_incremental(k, m, arrivals)
+        }
+    }
+
+    #[test]
+    fn a_failed_run_leaves_no_shards_for_the_next() {
+        let config = BenchConfig::new(2, 8192);
+        let data: Vec<u8> = (0..config.data_size).map(|i| (i % 241) as u8).collect();
+        let mut loopback = Loopback::new(Transport::Tcp, 3).unwrap();
+        for codec in codec::backends() {
+            let name = codec.name();
+            let corrupting = Corrupting(codec);
+            let err = loopback
+                .disseminate(&corrupting, &config, &data)
+                .unwrap_err();
+            assert!(err.to_string().contains("differs"), "{}: {}", name, err);
+            // The run failed at k shards; the rest must not reach this one
+            loopback
+                .disseminate(corrupting.0.as_ref(), &config, &data)
+                .unwrap();
+        }
+    }
+}
@@ -0,0 +1,800 @@
+//! Plain-text result tables in the spirit of divan's output (see `decode_results.txt`).
+
+use crate::affinity::Placement;
+use crate::codec::ErasureCodec;
+use crate::compat::{Compatibility, Parity};
+use crate::config::{format_size, BenchConfig};
+use crate::fuzz::{Malformation, Run};
+use crate::golden::Check;
+use crate::harness::Operation;
+use crate::misuse::{Entry, Misuse, Response};
+use crate::sampling::Sampling;
+use crate::stats::{format_duration, Distribution, PERCENTILES};
+use crate::tsc::Calibration;
+use std::fmt::Write;
+
+/// Placeholder for configurations a backend cannot run.
+const UNSUPPORTED: &str = "unsupported";
+
+/// Latency distribution of one backend, operation and configuration.
+pub struct LatencyRow {
+    pub backend: &'static str,
+    pub operation: Operation,
+    pub content: &'static str,
+    pub config: BenchConfig,
+    /// `None` when the backend does not support the configuration.
+    pub distribution: Option<Distribution>,
+    /// Median TSC ticks per sample; `None` without a TSC.
+    pub cycles: Option<u64>,
+}
+
+impl LatencyRow {
+    pub fn label(&self) -> String {
+        row_label(self.backend, self.operation, self.content, &self.config)
+    }
+}
+
+/// Warm- and cold-cache distributions of the same benchmark.
+pub struct CacheRow {
+    pub backend: &'static str,
+    pub operation: Operation,
+    pub content: &'static str,
+    pub config: BenchConfig,
+    /// Warm and cold distributions; `None` when the backend does not support the configuration.
+    pub timings: Option<(Distribution, Distribution)>,
+    /// Median TSC ticks per warm and per cold sample; `None` without a TSC.
+    pub cycles: (Option<u64>, Option<u64>),
+}
+
+impl CacheRow {
+    pub fn label(&self) -> String {
+        row_label(self.backend, self.operation, self.content, &self.config)
+    }
+}
+
+/// Large-committee measurements of one backend and configuration.
+pub struct LargeRow {
+    pub backend: &'static str,
+    pub config: BenchConfig,
+    /// `None` when the backend does not support the configuration.
+    pub timings: Option<LargeTimings>,
+}
+
+impl LargeRow {
+    pub fn label(&self) -> String {
+        format!(
+            "{}/F{}/{}",
+            self.backend,
+            self.config.f,
+            format_size(self.config.data_size)
+        )
+    }
+}
+
+/// What each node holds and how long the committee spends coding.
+pub struct LargeTimings {
+    /// Bytes of the shard sent to each node.
+    pub shard_size: usize,
+    pub encode: Distribution,
+    pub decode_f_missing: Distribution,
+    /// Median TSC ticks per encode and per decode; `None` without a TSC.
+    pub cycles: (Option<u64>, Option<u64>),
+}
+
+/// Early-completion decoding of one backend and configuration over seeded arrival orders.
+pub struct ArrivalRow {
+    pub backend: &'static str,
+    pub config: BenchConfig,
+    /// `None` when the backend does not support the configuration.
+    pub timings: Option<ArrivalTimings>,
+}
+
+impl ArrivalRow {
+    pub fn label(&self) -> String {
+        format!(
+            "{}/F{}/{}",
+            self.backend,
+            self.config.f,
+            format_size(self.config.data_size)
+        )
+    }
+}
+
+/// Simulated `k`-th arrival, measured decode and their sum, across replays.
+pub struct ArrivalTimings {
+    pub kth_arrival: Distribution,
+    pub decode: Distribution,
+    pub completion: Distribution,
+    /// Mean number of data shards the decode had to restore.
+    pub mean_missing_data: f64,
+}
+
+/// Simulated reliable broadcasts of one backend, configuration and fault scenario.
+pub struct BroadcastRow {
+    pub backend: &'static str,
+    pub sender: &'static str,
+    pub fault: &'static str,
+    pub faulty: usize,
+    /// All nodes, `3F` plus any extra ones.
+    pub nodes: usize,
+    pub config: BenchConfig,
+    /// `None` when the backend does not support the configuration.
+    pub timings: Option<BroadcastTimings>,
+}
+
+impl BroadcastRow {
+    pub fn label(&self) -> String {
+        format!(
+            "{}/{}/{}x{}/n{}/F{}/{}",
+            self.backend,
+            self.sender,
+            self.fault,
+            self.faulty,
+            self.nodes,
+            self.config.f,
+            format_size(self.config.data_size)
+        )
+    }
+}
+
+/// Protocol guarantees and costs across seeded runs.
+pub struct BroadcastTimings {
+    pub runs: usize,
+    /// Runs in which no honest node delivered a wrong payload.
+    pub safe: usize,
+    /// Runs in which every honest node delivered.
+    pub live: usize,
+    /// Simulated time of the last honest delivery.
+    pub completion: Distribution,
+    /// Mean fraction of CPU time spent encoding and decoding.
+    pub coding_share: f64,
+    pub messages: usize,
+}
+
+/// Availability sampling of one block per backend and configuration.
+pub struct SamplingRow {
+    pub backend: &'static str,
+    pub config: BenchConfig,
+    /// `None` when the backend does not support the configuration.
+    pub sampling: Option<Sampling>,
+}
+
+impl SamplingRow {
+    pub fn label(&self) -> String {
+        format!(
+            "{}/F{}/{}",
+            self.backend,
+            self.config.f,
+            format_size(self.config.data_size)
+        )
+    }
+}
+
+/// Loopback dissemination of one backend and configuration.
+pub struct NetworkRow {
+    pub backend: &'static str,
+    pub transport: &'static str,
+    pub config: BenchConfig,
+    /// `None` when the backend does not support the configuration.
+    pub timings: Option<NetworkTimings>,
+}
+
+impl NetworkRow {
+    pub fn label(&self) -> String {
+        format!(
+            "{}/{}/F{}/{}",
+            self.backend,
+            self.transport,
+            self.config.f,
+            format_size(self.config.data_size)
+        )
+    }
+}
+
+/// End-to-end latency from encode to decoded payload, and what crossed the sockets.
+pub struct NetworkTimings {
+    pub latency: Distribution,
+    /// Container bytes written per message: every shard plus its header.
+    pub wire_bytes: usize,
+}
+
+fn row_label(backend: &str, operation: Operation, content: &str, config: &BenchConfig) -> String {
+    format!(
+        "{}/{}/{}/F{}/{}",
+        backend,
+        operation,
+        content,
+        config.f,
+        format_size(config.data_size)
+    )
+}
+
+/// Renders percentiles and, below each row, its histogram buckets.
+pub fn render_latency(rows: &[LatencyRow], calibration: Option<&Calibration>) -> String {
+    let width = rows.iter().map(|row| row.label().len()).max().unwrap_or(0) + 2;
+
+    let mut out = render_calibration(calibration);
+    let _ = write!(out, "{:width$}", "latency");
+    for p in PERCENTILES {
+        let _ = write!(out, "│ {:<11}", format!("p{}", p));
+    }
+    let _ = writeln!(
+        out,
+        "│ {:<11}│ {:<9}│ {:<10}│ samples",
+        "max", "cyc/byte", "cyc/shard"
+    );
+
+    for row in rows {
+        let _ = write!(out, "{:width$}", row.label());
+        let Some(dist) = &row.distribution else {
+            let _ = writeln!(out, "│ {}", UNSUPPORTED);
+            continue;
+        };
+        for &p in PERCENTILES {
+            let _ = write!(out, "│ {:<11}", format_duration(dist.percentile(p)));
+        }
+        let (per_byte, per_shard) = cycle_columns(row.cycles, &row.config);
+        let _ = writeln!(
+            out,
+            "│ {:<11}│ {:<9}│ {:<10}│ {}",
+            format_duration(dist.max()),
+            per_byte,
+            per_shard,
+            dist.len()
+        );
+
+        for bucket in dist.histogram() {
+            let share = bucket.count as f64 / dist.len() as f64;
+            let _ = writeln!(
+                out,
+                "    [{:>9}, {:>9})  {:>6}  {:>6.2}%  {}",
+                format_duration(bucket.lower),
+                format_duration(bucket.upper),
+                bucket.count,
+                share * 100.0,
+                "#".repeat((share * 50.0).round() as usize)
+            );
+        }
+    }
+    out
+}
+
+/// Renders where the runner was placed, and anything that may have added noise.
+pub fn render_placement(placement: &Placement) -> String {
+    let mut line = match placement.cpu {
+        Some(cpu) if placement.pinned => format!("cpu{} pinned", cpu),
+        Some(cpu) => format!("cpu{} unpinned", cpu),
+        None => "cpu unknown".to_string(),
+    };
+    if let Some(nice) = placement.nice {
+        let _ = write!(line, ", nice {}", nice);
+    }
+    let _ = write!(
+        line,
+        ", governor {}",
+        placement.governor.as_deref().unwrap_or("unknown")
+    );
+    for &(sibling, busy) in &placement.siblings {
+        let _ = match busy {
+            Some(busy) => write!(line, ", sibling cpu{} {:.0}% busy", sibling, busy * 100.0),
+            None => write!(line, ", sibling cpu{}", sibling),
+        };
+    }
+
+    let mut out = String::new();
+    let _ = writeln!(out, "{}", line);
+    for warning in placement.warnings() {
+        let _ = writeln!(out, "warning: {}", warning);
+    }
+    out
+}
+
+/// Renders the TSC rate cycle counts were taken at, and why they may not compare.
+pub fn render_calibration(calibration: Option<&Calibration>) -> String {
+    let mut out = String::new();
+    let Some(calibration) = calibration else {
+        let _ = writeln!(out, "tsc unavailable; cycle counts omitted");
+        return out;
+    };
+    let (min, max) = calibration.core_range();
+    let _ = writeln!(
+        out,
+        "tsc {:.3} GHz{}, core {:.2}-{:.2} GHz",
+        calibration.tsc_hz / 1e9,
+        if calibration.invariant {
+            " invariant"
+        } else {
+            ""
+        },
+        min / 1e9,
+        max / 1e9
+    );
+    for warning in calibration.warnings() {
+        let _ = writeln!(out, "warning: {}", warning);
+    }
+    out
+}
+
+/// Median cycles over the payload and over every shard of the codeword.
+fn cycle_columns(cycles: Option<u64>, config: &BenchConfig) -> (String, String) {
+    match cycles {
+        Some(cycles) => (
+            format!("{:.2}", cycles as f64 / config.data_size.max(1) as f64),
+            format!("{:.0}", cycles as f64 / config.total_shards() as f64),
+        ),
+        None => ("n/a".to_string(), "n/a".to_string()),
+    }
+}
+
+/// Renders warm and cold medians and p99s side by side, with the cold/warm ratio.
+pub fn render_cache_comparison(rows: &[CacheRow], calibration: Option<&Calibration>) -> String {
+    let width = rows.iter().map(|row| row.label().len()).max().unwrap_or(0) + 2;
+
+    let mut out = render_calibration(calibration);
+    let _ = writeln!(
+        out,
+        "{:width$}│ {:<11}│ {:<11}│ {:<11}│ {:<11}│ {:<10}│ {:<13}│ cyc/shard w/c",
+        "cache", "warm p50", "cold p50", "warm p99", "cold p99", "cold/warm", "cyc/byte w/c"
+    );
+    for row in rows {
+        let Some((warm, cold)) = &row.timings else {
+            let _ = writeln!(out, "{:width$}│ {}", row.label(), UNSUPPORTED);
+            continue;
+        };
+        let ratio = cold.percentile(50.0).as_secs_f64() / warm.percentile(50.0).as_secs_f64();
+        let (warm_per_byte, warm_per_shard) = cycle_columns(row.cycles.0, &row.config);
+        let (cold_per_byte, cold_per_shard) = cycle_columns(row.cycles.1, &row.config);
+        let _ = writeln!(
+            out,
+            "{:width$}│ {:<11}│ {:<11}│ {:<11}│ {:<11}│ {:<10}│ {:<13}│ {}/{}",
+            row.label(),
+            format_duration(warm.percentile(50.0)),
+            format_duration(cold.percentile(50.0)),
+            format_duration(warm.percentile(99.0)),
+            format_duration(cold.percentile(99.0)),
+            format!("{:.2}x", ratio),
+            format!("{}/{}", warm_per_byte, cold_per_byte),
+            warm_per_shard,
+            cold_per_shard
+        );
+    }
+    out
+}
+
+/// Renders committee size, per-node shard size and encode/decode medians,
+/// with the cycles of each as encode/decode pairs.
+pub fn render_large(rows: &[LargeRow], calibration: Option<&Calibration>) -> String {
+    let width = rows.iter().map(|row| row.label().len()).max().unwrap_or(0) + 2;
+
+    let mut out = render_calibration(calibration);
+    let _ = writeln!(
+        out,
+        "{:width$}│ {:<7}│ {:<11}│ {:<11}│ {:<17}│ {:<13}│ cyc/shard e/d",
+        "large", "nodes", "shard/node", "encode", "decode_f_missing", "cyc/byte e/d"
+    );
+    for row in rows {
+        let _ = write!(
+            out,
+            "{:width$}│ {:<7}│ ",
+            row.label(),
+            row.config.total_shards()
+        );
+        let Some(timings) = &row.timings else {
+            let _ = writeln!(out, "{}", UNSUPPORTED);
+            continue;
+        };
+        let (encode_per_byte, encode_per_shard) = cycle_columns(timings.cycles.0, &row.config);
+        let (decode_per_byte, decode_per_shard) = cycle_columns(timings.cycles.1, &row.config);
+        let _ = writeln!(
+            out,
+            "{:<11}│ {:<11}│ {:<17}│ {:<13}│ {}/{}",
+            format_size(timings.shard_size),
+            format_duration(timings.encode.percentile(50.0)),
+            format_duration(timings.decode_f_missing.percentile(50.0)),
+            format!("{}/{}", encode_per_byte, decode_per_byte),
+            encode_per_shard,
+            decode_per_shard
+        );
+    }
+    out
+}
+
+/// Renders when the `k`-th shard arrived, how long decoding took from there, and the total.
+pub fn render_arrivals(rows: &[ArrivalRow]) -> String {
+    let width = rows.iter().map(|row| row.label().len()).max().unwrap_or(0) + 2;
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "{:width$}│ {:<11}│ {:<11}│ {:<11}│ {:<11}│ missing data",
+        "arrival", "k-th p50", "decode p50", "decode p99", "done p99"
+    );
+    for row in rows {
+        let Some(timings) = &row.timings else {
+            let _ = writeln!(out, "{:width$}│ {}", row.label(), UNSUPPORTED);
+            continue;
+        };
+        let _ = writeln!(
+            out,
+            "{:width$}│ {:<11}│ {:<11}│ {:<11}│ {:<11}│ {:.1}",
+            row.label(),
+            format_duration(timings.kth_arrival.percentile(50.0)),
+            format_duration(timings.decode.percentile(50.0)),
+            format_duration(timings.decode.percentile(99.0)),
+            format_duration(timings.completion.percentile(99.0)),
+            timings.mean_missing_data
+        );
+    }
+    out
+}
+
+/// Renders safety and liveness counts, simulated completion and the coding share.
+pub fn render_broadcast(rows: &[BroadcastRow]) -> String {
+    let width = rows.iter().map(|row| row.label().len()).max().unwrap_or(0) + 2;
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "{:width$}│ {:<8}│ {:<8}│ {:<11}│ {:<11}│ {:<8}│ messages",
+        "broadcast", "safe", "live", "done p50", "done p99", "coding"
+    );
+    for row in rows {
+        let Some(timings) = &row.timings else {
+            let _ = writeln!(out, "{:width$}│ {}", row.label(), UNSUPPORTED);
+            continue;
+        };
+        let _ = writeln!(
+            out,
+            "{:width$}│ {:<8}│ {:<8}│ {:<11}│ {:<11}│ {:<8}│ {}",
+            row.label(),
+            format!("{}/{}", timings.safe, timings.runs),
+            format!("{}/{}", timings.live, timings.runs),
+            format_duration(timings.completion.percentile(50.0)),
+            format_duration(timings.completion.percentile(99.0)),
+            format!("{:.1}%", timings.coding_share * 100.0),
+            timings.messages
+        );
+    }
+    out
+}
+
+/// Renders producer and client costs, then detection rates per sample count.
+pub fn render_sampling(rows: &[SamplingRow]) -> String {
+    let width = rows.iter().map(|row| row.label().len()).max().unwrap_or(0) + 2;
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "{:width$}│ {:<10}│ {:<12}│ {:<11}│ {:<11}│ {:<11}│ proof",
+        "sampling", "withheld", "decodable", "encode", "commit", "verify"
+    );
+    for row in rows {
+        let Some(sampling) = &row.sampling else {
+            let _ = writeln!(out, "{:width$}│ {}", row.label(), UNSUPPORTED);
+            continue;
+        };
+        let _ = writeln!(
+            out,
+            "{:width$}│ {:<10}│ {:<12}│ {:<11}│ {:<11}│ {:<11}│ {}B",
+            row.label(),
+            format!("{}/{}", sampling.withheld, row.config.total_shards()),
+            if sampling.recoverable { "yes" } else { "no" },
+            format_duration(sampling.encode),
+            format_duration(sampling.commit),
+            format_duration(sampling.verify),
+            sampling.proof_bytes
+        );
+        for detection in &sampling.detections {
+            let _ = writeln!(
+                out,
+                "    {:>4} samples   empirical {:>7.3}%   analytical {:>7.3}%",
+                detection.samples,
+                detection.empirical * 100.0,
+                detection.analytical * 100.0
+            );
+        }
+    }
+    out
+}
+
+/// Renders latency percentiles with the payload and wire throughput at the median.
+pub fn render_network(rows: &[NetworkRow]) -> String {
+    let width = rows.iter().map(|row| row.label().len()).max().unwrap_or(0) + 2;
+    let per_second = |bytes: usize, elapsed: std::time::Duration| {
+        bytes as f64 / elapsed.as_secs_f64().max(f64::MIN_POSITIVE) / 1e6
+    };
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "{:width$}│ {:<11}│ {:<11}│ {:<12}│ {:<12}│ wire bytes",
+        "network", "p50", "p99", "goodput", "wire rate"
+    );
+    for row in rows {
+        let Some(timings) = &row.timings else {
+            let _ = writeln!(out, "{:width$}│ {}", row.label(), UNSUPPORTED);
+            continue;
+        };
+        let median = timings.latency.percentile(50.0);
+        let _ = writeln!(
+            out,
+            "{:width$}│ {:<11}│ {:<11}│ {:<12}│ {:<12}│ {}",
+            row.label(),
+            format_duration(median),
+            format_duration(timings.latency.percentile(99.0)),
+            format!("{:.1} MB/s", per_second(row.config.data_size, median)),
+            format!("{:.1} MB/s", per_second(timings.wire_bytes, median)),
+            format_size(timings.wire_bytes)
+        );
+    }
+    out
+}
+
+/// Renders each backend's systematic-ness, then an encoder × decoder matrix
+/// of parity agreement; `+` marks pairs that decoded and `!` pairs that failed.
+pub fn render_compat(compat: &Compatibility) -> String {
+    let width = compat
+        .backends
+        .iter()
+        .map(|name| name.len())
+        .max()
+        .unwrap_or(0)
+        + 2;
+    let column = width.max(12);
+
+    let mut out = String::new();
+    let _ = writeln!(out, "{:width$}│ systematic", "backend");
+    for (name, systematic) in compat.backends.iter().zip(&compat.systematic) {
+        let verdict = match systematic {
+            Some(true) => "yes",
+            Some(false) => "no",
+            None => UNSUPPORTED,
+        };
+        let _ = writeln!(out, "{:width$}│ {}", name, verdict);
+    }
+
+    let _ = writeln!(out);
+    let line = |first: &str, cells: &[String]| {
+        let cells: Vec<String> = cells
+            .iter()
+            .map(|cell| format!("{:column$}", cell))
+            .collect();
+        format!("{:width$}│ {}", first, cells.join("│ ").trim_end())
+    };
+    let names: Vec<String> = compat
+        .backends
+        .iter()
+        .map(|name| name.to_string())
+        .collect();
+    let _ = writeln!(out, "{}", line("enc \\ dec", &names));
+    for (a, encoder) in compat.backends.iter().enumerate() {
+        let cells: Vec<String> = (0..compat.backends.len())
+            .map(|b| {
+                let pair = compat.pair(a, b);
+                let mark = match (pair.parity, pair.decodes) {
+                    (Parity::Shape | Parity::Untested, _) | (_, None) => "",
+                    (_, Some(true)) => " +",
+                    (_, Some(false)) => " !",
+                };
+                format!("{}{}", pair.parity.name(), mark)
+            })
+            .collect();
+        let _ = writeln!(out, "{}", line(encoder, &cells));
+    }
+    out
+}
+
+/// Renders each golden-vector comparison with the shard layout it produced.
+pub fn render_golden(checks: &[Check]) -> String {
+    let label = |check: &Check| {
+        format!(
+            "{}/F{}/{}",
+            check.actual.backend,
+            check.actual.config.f,
+            format_size(check.actual.config.data_size)
+        )
+    };
+    let width = checks
+        .iter()
+        .map(|check| label(check).len())
+        .max()
+        .unwrap_or(0)
+        + 2;
+
+    let mut out = String::new();
+    let _ = writeln!(out, "{:width$}│ {:<22}│ shards", "golden", "status");
+    for check in checks {
+        let _ = writeln!(
+            out,
+            "{:width$}│ {:<22}│ {} x {}B",
+            label(check),
+            check.status(),
+            check.actual.shards,
+            check.actual.shard_len
+        );
+    }
+    out
+}
+
+/// Renders fuzz cases and calls per backend and malformation, then every failing case.
+pub fn render_fuzz(runs: &[Run]) -> String {
+    let mut backends: Vec<&str> = runs.iter().map(|run| run.case.backend).collect();
+    backends.dedup();
+    let label = |backend: &str, malformation: Malformation| format!("{}/{}", backend, malformation);
+    let width = backends
+        .iter()
+        .flat_map(|backend| Malformation::ALL.iter().map(|&m| label(backend, m).len()))
+        .max()
+        .unwrap_or(0)
+        + 2;
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "{:width$}│ {:<7}│ {:<7}│ {:<10}│ failed",
+        "fuzz", "cases", "calls", "rejected"
+    );
+    for backend in &backends {
+        for &malformation in Malformation::ALL {
+            let cases: Vec<&Run> = runs
+                .iter()
+                .filter(|run| run.case.backend == *backend && run.case.malformation == malformation)
+                .collect();
+            let outcomes = cases.iter().flat_map(|run| &run.outcomes);
+            let calls = outcomes.clone().count();
+            let failed = outcomes.filter(|(_, outcome)| outcome.is_failure()).count();
+            let _ = writeln!(
+                out,
+                "{:width$}│ {:<7}│ {:<7}│ {:<10}│ {}",
+                label(backend, malformation),
+                cases.len(),
+                calls,
+                calls - failed,
+                failed
+            );
+        }
+    }
+    for run in runs.iter().filter(|run| run.failed()) {
+        let _ = writeln!(out, "FAILED {}", run.corpus_line());
+    }
+    out
+}
+
+/// Renders the error kind each backend returns per misuse, then every message.
+pub fn render_catalogue(entries: &[Entry]) -> String {
+    let mut backends: Vec<&str> = Vec::new();
+    for entry in entries {
+        if !backends.contains(&entry.backend) {
+            backends.push(entry.backend);
+        }
+    }
+    let width = Misuse::ALL
+        .iter()
+        .map(|misuse| misuse.name().len())
+        .max()
+        .unwrap_or(0)
+        + 2;
+    let column = "unsupported_operation".len() + 1;
+    let response = |misuse: Misuse, backend: &str| {
+        entries
+            .iter()
+            .find(|entry| entry.misuse == misuse && entry.backend == backend)
+            .map(|entry| &entry.response)
+    };
+
+    let mut out = String::new();
+    let line = |first: &str, cells: Vec<&str>| {
+        let cells: Vec<String> = cells
+            .iter()
+            .map(|cell| format!("{:column$}", cell))
+            .collect();
+        format!("{:width$}│ {}", first, cells.join("│ ").trim_end())
+    };
+    let _ = writeln!(out, "{}", line("misuse", backends.clone()));
+    for &misuse in Misuse::ALL {
+        let cells = backends
+            .iter()
+            .map(|&backend| response(misuse, backend).map_or("", Response::label))
+            .collect();
+        let _ = writeln!(out, "{}", line(misuse.name(), cells));
+    }
+
+    for &misuse in Misuse::ALL {
+        let _ = writeln!(out);
+        let _ = writeln!(out, "{}: {}", misuse.name(), misuse.description());
+        for &backend in &backends {
+            let detail = match response(misuse, backend) {
+                Some(Response::Rejected { message, .. }) => message.as_str(),
+                Some(Response::Accepted) => "accepted without an error",
+                Some(Response::Panicked) => "panicked",
+                None => continue,
+            };
+            let _ = writeln!(out, "  {:14}{}", backend, detail);
+        }
+    }
+    out
+}
+
+/// Renders the zero padding [`BenchConfig::shard_size`] adds to each payload,
+/// next to the least each backend's own alignment would need.
+pub fn render_padding(codecs: &[Box<dyn ErasureCodec>], configs: &[BenchConfig]) -> String {
+    let label = |config: &BenchConfig| format!("F{}/{}", config.f, format_size(config.data_size));
+    let width = configs
+        .iter()
+        .map(|config| label(config).len())
+        .max()
+        .unwrap_or(0)
+        + 2;
+    let column = codecs
+        .iter()
+        .map(|codec| codec.label().len() + "min ".len())
+        .max()
+        .unwrap_or(0)
+        .max(UNSUPPORTED.len())
+        + 1;
+
+    let mut out = String::new();
+    let mut header = format!(
+        "{:width$}│ {:<8}│ {:<8}│ {:<9}",
+        "padding", "shard", "padded", "overhead"
+    );
+    for codec in codecs {
+        let _ = write!(header, "│ {:<column$}", format!("min {}", codec.label()));
+    }
+    let _ = writeln!(out, "{}", header.trim_end());
+    for config in configs {
+        let overhead = 100.0 * config.padding() as f64 / config.data_size as f64;
+        let mut line = format!(
+            "{:width$}│ {:<8}│ {:<8}│ {:<9}",
+            label(config),
+            config.shard_size(),
+            config.padding(),
+            format!("{:.1}%", overhead)
+        );
+        for codec in codecs {
+            let minimum = if codec.supports(config.data_shards(), config.coding_shards()) {
+                codec
+                    .capabilities()
+                    .min_padding(config.data_size, config.data_shards())
+                    .to_string()
+            } else {
+                UNSUPPORTED.to_string()
+            };
+            let _ = write!(line, "│ {:<column$}", minimum);
+        }
+        let _ = writeln!(out, "{}", line.trim_end());
+    }
+    out
+}
+
+/// Renders each backend's advertised limits.
+pub fn render_capabilities(codecs: &[Box<dyn ErasureCodec>]) -> String {
+    let width = codecs
+        .iter()
+        .map(|codec| codec.label().len())
+        .max()
+        .unwrap_or(0)
+        .max(12)
+        + 2;
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "{:width$}│ {:<10}│ {:<12}│ {:<11}│ {:<10}│ min shard",
+        "backend", "max data", "max parity", "max total", "alignment"
+    );
+    for codec in codecs {
+        let caps = codec.capabilities();
+        let alignment = format!("{}B", caps.shard_alignment);
+        let _ = writeln!(
+            out,
+            "{:width$}│ {:<10}│ {:<12}│ {:<11}│ {:<10}│ {}B",
+            codec.label(),
+            caps.max_data_shards,
+            caps.max_parity_shards,
+            caps.max_total_shards,
+            alignment,
+            caps.min_shard_size
+        );
+    }
+    out
+}
@@ -0,0 +1,210 @@
+//! Data-availability sampling: light clients probing an erasure-coded block.
+//!
+//! The producer encodes a block into `n = 3F` shards and commits to them with
+//! a [Merkle root](crate::commitment). An adversary withholds some shards;
+//! once more than `n - k = 2F` are gone the block can no longer be decoded.
+//! Each client asks for random distinct shards and checks the inclusion proof
+//! of every one it gets. A single withheld shard among its samples means the
+//! client detects that the block is unavailable.
+
+use crate::codec::{self, check_indices, ErasureCodec};
+use crate::commitment::MerkleTree;
+use crate::config::BenchConfig;
+use crate::rng::Rng;
+use std::time::{Duration, Instant};
+
+/// Runs of the producer's encode and commit; the fastest is reported.
+pub const COST_RUNS: usize = 5;
+
+/// Shards an adversary must withhold so that the block cannot be decoded.
+pub fn minimal_withholding(config: &BenchConfig) -> usize {
+    config.coding_shards() + 1
+}
+
+/// Chance that `samples` distinct uniform draws from `total` shards hit at
+/// least one of `withheld`: `1 - C(total - withheld, samples) / C(total, samples)`.
+pub fn detection_probability(total: usize, withheld: usize, samples: usize) -> f64 {
+    if samples > total - withheld.min(total) {
+        return 1.0;
+    }
+    let missed: f64 = (0..samples)
+        .map(|i| (total - withheld - i) as f64 / (total - i) as f64)
+        .product();
+    1.0 - missed
+}
+
+/// Empirical and analytical detection for one number of samples per client.
+#[derive(Debug, Clone, Copy)]
+pub struct Detection {
+    pub samples: usize,
+    /// Fraction of simulated clients that hit a withheld shard.
+    pub empirical: f64,
+    pub analytical: f64,
+}
+
+/// Outcome of sampling one block.
+#[derive(Debug, Clone)]
+pub struct Sampling {
+    pub withheld: usize,
+    /// Whether the shards left are still enough to decode the block.
+    pub recoverable: bool,
+    pub detections: Vec<Detection>,
+    /// Fastest of [`COST_RUNS`] encodes of the block.
+    pub encode: Duration,
+    /// Fastest of [`COST_RUNS`] Merkle tree builds over all shards.
+    pub commit: Duration,
+    /// Mean time a client spends checking one inclusion proof.
+    pub verify: Duration,
+    /// Proof bytes sent with each sampled shard.
+    pub proof_bytes: usize,
+}
+
+/// Encodes `data`, withholds the shards in `withheld` and lets `clients`
+/// clients draw each of `sample_counts` shards at random.
+///
+/// Counts above the number of shards are capped, then sorted and deduplicated,
+/// which is the order of [`Sampling::detections`]. Every client draws its
+/// samples in one random order and its `s`-sample run is the first `s`, so
+/// larger counts never detect less than smaller ones for the same client.
+pub fn simulate(
+    codec: &dyn ErasureCodec,
+    config: &BenchConfig,
+    data: &[u8],
+    withheld: &[usize],
+    sample_counts: &[usize],
+    clients: usize,
+    seed: u64,
+) -> codec::Result<Sampling> {
+    let (k, m) = (config.data_shards(), config.coding_shards());
+    let total = k + m;
+    check_indices(withheld, total)?;
+    let mut is_withheld = vec![false; total];
+    for &index in withheld {
+        is_withheld[index] = true;
+    }
+    let withheld_count = is_withheld.iter().filter(|&&hidden| hidden).count();
+
+    let (shards, encode) = fastest(|| codec.encode(k, m, data))?;
+    let (tree, commit) = fastest(|| Ok(MerkleTree::new(&shards)))?;
+    let root = tree.root();
+    let proofs: Vec<_> = (0..total)
+        .map(|index| tree.proof(index).expect("one leaf per shard"))
+        .collect();
+
+    let mut counts: Vec<usize> = sample_counts.iter().map(|&s| s.min(total)).collect();
+    counts.sort_unstable();
+    counts.dedup();
+    let most = counts.iter().copied().max().unwrap_or(0);
+    let mut detected = vec![0usize; counts.len()];
+    let (mut verified, mut verifying) = (0usize, Duration::ZERO);
+    let mut rng = Rng::new(seed);
+    let mut order: Vec<usize> = (0..total).collect();
+    for _ in 0..clients {
+        // Partial Fisher-Yates: the first `most` entries become the draws
+        for i in 0..most {
+            let j = i + rng.below((total - i) as u64) as usize;
+            order.swap(i, j);
+        }
+        let mut first_miss = None;
+        for (draw, &index) in order[..most].iter().enumerate() {
+            if is_withheld[index] {
+                first_miss = Some(draw);
+                break;
+            }
+            let start = Instant::now();
+            let valid = proofs[index].verify(&root, shards.len(), &shards[index]);
+            verifying += start.elapsed();
+            verified += 1;
+            if !valid {
+                return Err(format!("inclusion proof of shard {} rejected", index).into());
+            }
+        }
+        for (hits, &count) in detected.iter_mut().zip(&counts) {
+            if first_miss.is_some_and(|draw| draw < count) {
+                *hits += 1;
+            }
+        }
+    }
+
+    let detections = counts
+        .iter()
+        .zip(detected)
+        .map(|(&samples, hits)| Detection {
+            samples,
+            empirical: hits as f64 / clients.max(1) as f64,
+            analytical: detection_probability(total, withheld_count, samples),
+        })
+        .collect();
+    Ok(Sampling {
+        withheld: withheld_count,
+        recoverable: total - withheld_count >= k,
+        detections,
+        encode,
+        commit,
+        verify: verifying / verified.max(1) as u32,
+        proof_bytes: proofs[0].encoded_len(),
+    })
+}
+
+/// Result and duration of the fastest of [`COST_RUNS`] calls to `run`.
+fn fastest<T>(mut run: impl FnMut() -> codec::Result<T>) -> codec::Result<(T, Duration)> {
+    let mut best: Option<(T, Duration)> = None;
+    for _ in 0..COST_RUNS {
+        let start = Instant::now();
+        let value = run()?;
+        let elapsed = start.elapsed();
+        if best.as_ref().is_none_or(|(_, fastest)| elapsed < *fastest) {
+            best = Some((value, elapsed));
+        }
+    }
+    Ok(best.expect("at least one run"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn analytical_probability_matches_hand_computed_cases() {
+        assert_eq!(detection_probability(9, 0, 3), 0.0);
+        assert_eq!(detection_probability(9, 7, 3), 1.0);
+        // One sample: the withheld fraction
+        assert!((detection_probability(30, 21, 1) - 0.7).abs() < 1e-12);
+        // 1 - C(4, 2) / C(6, 2) = 1 - 6 / 15
+        assert!((detection_probability(6, 2, 2) - 0.6).abs() < 1e-12);
+    }
+
+    #[test]
+    fn empirical_detection_tracks_the_analytical_rate() {
+        let config = BenchConfig::new(5, 4096);
+        let data: Vec<u8> = (0..config.data_size).map(|i| (i % 233) as u8).collect();
+        let withheld: Vec<usize> = (0..minimal_withholding(&config)).collect();
+        for codec in codec::backends() {
+            let sampling = simulate(
+                codec.as_ref(),
+                &config,
+                &data,
+                &withheld,
+                &[1, 3, 100],
+                2000,
+                9,
+            )
+            .unwrap();
+            assert!(!sampling.recoverable, "{}", codec.name());
+            assert_eq!(sampling.detections[2].samples, config.total_shards());
+            for detection in &sampling.detections {
+                assert!(
+                    (detection.empirical - detection.analytical).abs() < 0.05,
+                    "{} {:?}",
+                    codec.name(),
+                    detection
+                );
+            }
+            assert_eq!(sampling.detections[2].empirical, 1.0);
+        }
+        if let Some(codec) = codec::backends().first() {
+            let out_of_range = [config.total_shards()];
+            assert!(simulate(codec.as_ref(), &config, &data, &out_of_range, &[1], 1, 0).is_err());
+        }
+    }
+}
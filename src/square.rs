@@ -0,0 +1,314 @@
+//! Two-dimensional Reed-Solomon extension, as in Celestia's data squares.
+//!
+//! A k×k square of equal-sized shares is extended to 2k×2k: every original
+//! row is encoded into `2k` shares, then every one of the `2k` columns. Any
+//! backend can do this whose shards are its input shares, verbatim and in
+//! order; with a linear code the parity rows are then codewords of the row
+//! code as well, so a missing share can be restored along its row or its
+//! column. Repair alternates the two until the square is whole or stuck.
+
+use crate::codec::{self, ErasureCodec};
+
+/// A 2k×2k square: the original shares top-left, row parity top-right,
+/// column parity below.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtendedSquare {
+    width: usize,
+    /// Row-major, `width * width` shares.
+    cells: Vec<Vec<u8>>,
+}
+
+impl ExtendedSquare {
+    /// Shares per row and column: `2k`.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn cell(&self, row: usize, column: usize) -> &[u8] {
+        &self.cells[row * self.width + column]
+    }
+
+    pub fn cells(&self) -> &[Vec<u8>] {
+        &self.cells
+    }
+
+    /// The original k×k shares, row by row.
+    pub fn original(&self) -> Vec<Vec<u8>> {
+        let k = self.width / 2;
+        (0..k)
+            .flat_map(|row| (0..k).map(move |column| (row, column)))
+            .map(|(row, column)| self.cell(row, column).to_vec())
+            .collect()
+    }
+
+    /// Every share, with those at `missing` (row, column) positions withheld.
+    pub fn withhold(
+        &self,
+        missing: impl IntoIterator<Item = (usize, usize)>,
+    ) -> Vec<Option<Vec<u8>>> {
+        let mut cells: Vec<_> = self.cells.iter().cloned().map(Some).collect();
+        for (row, column) in missing {
+            cells[row * self.width + column] = None;
+        }
+        cells
+    }
+}
+
+/// Outcome of [`SquareCodec::repair`].
+#[derive(Debug, Clone)]
+pub struct Repair {
+    pub square: ExtendedSquare,
+    /// Row-then-column sweeps needed; 0 when nothing was missing.
+    pub rounds: usize,
+}
+
+/// 2D extension over one backend, for k×k squares of `share_size`-byte shares.
+pub struct SquareCodec<'a> {
+    codec: &'a dyn ErasureCodec,
+    k: usize,
+    share_size: usize,
+}
+
+impl<'a> SquareCodec<'a> {
+    /// Checks that `codec` can extend k×k squares of `share_size`-byte shares.
+    ///
+    /// Besides the shard limits this probes one encode: backends that pad,
+    /// interleave or transform their input have no original square to extend.
+    pub fn new(codec: &'a dyn ErasureCodec, k: usize, share_size: usize) -> codec::Result<Self> {
+        let caps = codec.capabilities();
+        if !codec.supports(k, k) {
+            return Err(format!("{} cannot encode {} + {} shards", codec.name(), k, k).into());
+        }
+        if share_size == 0
+            || !share_size.is_multiple_of(caps.shard_alignment)
+            || share_size < caps.min_shard_size
+        {
+            return Err(format!(
+                "{} needs shares of at least {}B in multiples of {}B, not {}B",
+                codec.name(),
+                caps.min_shard_size,
+                caps.shard_alignment,
+                share_size
+            )
+            .into());
+        }
+        let probe: Vec<u8> = (0..k * share_size).map(|i| (i % 251) as u8 + 1).collect();
+        let shards = codec.encode(k, k, &probe)?;
+        let verbatim = shards.len() == 2 * k
+            && shards[..k]
+                .iter()
+                .zip(probe.chunks(share_size))
+                .all(|(shard, share)| shard == share);
+        if !verbatim {
+            return Err(format!(
+                "{} does not keep its input shares verbatim, so it cannot extend a square",
+                codec.name()
+            )
+            .into());
+        }
+        Ok(Self {
+            codec,
+            k,
+            share_size,
+        })
+    }
+
+    /// Extends `shares`, the k×k original square in row-major order.
+    pub fn extend(&self, shares: &[Vec<u8>]) -> codec::Result<ExtendedSquare> {
+        let (k, width) = (self.k, 2 * self.k);
+        if shares.len() != k * k || shares.iter().any(|share| share.len() != self.share_size) {
+            return Err(format!(
+                "expected {} shares of {}B for a {}x{} square",
+                k * k,
+                self.share_size,
+                k,
+                k
+            )
+            .into());
+        }
+        let mut cells = vec![Vec::new(); width * width];
+        for (row, originals) in shares.chunks(k).enumerate() {
+            let extended = self.codec.encode(k, k, &originals.concat())?;
+            self.place(&mut cells, extended, |column| row * width + column)?;
+        }
+        for column in 0..width {
+            let top: Vec<u8> = (0..k)
+                .flat_map(|row| cells[row * width + column].iter().copied())
+                .collect();
+            let extended = self.codec.encode(k, k, &top)?;
+            self.place(&mut cells, extended, |row| row * width + column)?;
+        }
+        Ok(ExtendedSquare { width, cells })
+    }
+
+    /// Restores every missing share of a 2k×2k square, given row-major as
+    /// `Some(share)` or `None`.
+    ///
+    /// Each round reconstructs every incomplete row with at least `k` shares,
+    /// then every such column. Fails once a round restores nothing, e.g. when
+    /// a (k+1)×(k+1) block is missing.
+    pub fn repair(&self, cells: &[Option<Vec<u8>>]) -> codec::Result<Repair> {
+        let width = 2 * self.k;
+        if cells.len() != width * width {
+            return Err(format!("expected {} shares, got {}", width * width, cells.len()).into());
+        }
+        let mut cells = cells.to_vec();
+        let mut rounds = 0;
+        while cells.iter().any(Option::is_none) {
+            let before = cells.iter().filter(|cell| cell.is_none()).count();
+            rounds += 1;
+            self.repair_lines(&mut cells, |row, i| row * width + i)?;
+            self.repair_lines(&mut cells, |column, i| i * width + column)?;
+            let after = cells.iter().filter(|cell| cell.is_none()).count();
+            if after == before {
+                return Err(format!(
+                    "square unrepairable: {} shares missing after {} rounds",
+                    after, rounds
+                )
+                .into());
+            }
+        }
+        let cells = cells.into_iter().map(Option::unwrap).collect();
+        Ok(Repair {
+            square: ExtendedSquare { width, cells },
+            rounds,
+        })
+    }
+
+    /// Reconstructs every line (row or column, per `at(line, i)`) that is
+    /// incomplete but has at least `k` shares.
+    fn repair_lines(
+        &self,
+        cells: &mut [Option<Vec<u8>>],
+        at: impl Fn(usize, usize) -> usize,
+    ) -> codec::Result<()> {
+        let (k, width) = (self.k, 2 * self.k);
+        for line in 0..width {
+            let shares: Vec<Option<Vec<u8>>> =
+                (0..width).map(|i| cells[at(line, i)].clone()).collect();
+            let present = shares.iter().filter(|share| share.is_some()).count();
+            if present == width || present < k {
+                continue;
+            }
+            let restored = self.codec.reconstruct(k, k, &shares)?;
+            self.place_missing(cells, restored, |i| at(line, i))?;
+        }
+        Ok(())
+    }
+
+    fn place(
+        &self,
+        cells: &mut [Vec<u8>],
+        shards: Vec<Vec<u8>>,
+        at: impl Fn(usize) -> usize,
+    ) -> codec::Result<()> {
+        self.check_line(&shards)?;
+        for (i, shard) in shards.into_iter().enumerate() {
+            cells[at(i)] = shard;
+        }
+        Ok(())
+    }
+
+    fn place_missing(
+        &self,
+        cells: &mut [Option<Vec<u8>>],
+        shards: Vec<Vec<u8>>,
+        at: impl Fn(usize) -> usize,
+    ) -> codec::Result<()> {
+        self.check_line(&shards)?;
+        for (i, shard) in shards.into_iter().enumerate() {
+            cells[at(i)].get_or_insert(shard);
+        }
+        Ok(())
+    }
+
+    /// A line from the backend must be `2k` shares of `share_size` bytes.
+    fn check_line(&self, shards: &[Vec<u8>]) -> codec::Result<()> {
+        if shards.len() != 2 * self.k || shards.iter().any(|shard| shard.len() != self.share_size) {
+            return Err(format!("{} changed the share layout", self.codec.name()).into());
+        }
+        Ok(())
+    }
+}
+
+/// Whether `codec` can extend k×k squares of `share_size`-byte shares.
+pub fn supports(codec: &dyn ErasureCodec, k: usize, share_size: usize) -> bool {
+    SquareCodec::new(codec, k, share_size).is_ok()
+}
+
+/// Splits `data` into `share_size`-byte shares, the last zero-padded.
+pub fn shares(data: &[u8], share_size: usize) -> Vec<Vec<u8>> {
+    data.chunks(share_size)
+        .map(|chunk| {
+            let mut share = chunk.to_vec();
+            share.resize(share_size, 0);
+            share
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::backends;
+    use crate::rng::Rng;
+
+    const SHARE_SIZE: usize = 128;
+
+    fn original(k: usize) -> Vec<Vec<u8>> {
+        let mut data = vec![0u8; k * k * SHARE_SIZE];
+        Rng::new(k as u64).fill_bytes(&mut data);
+        shares(&data, SHARE_SIZE)
+    }
+
+    #[test]
+    fn extends_and_repairs_partial_squares() {
+        let k = 4;
+        let shares = original(k);
+        for codec in backends() {
+            let Ok(square_codec) = SquareCodec::new(codec.as_ref(), k, SHARE_SIZE) else {
+                // novelpoly interleaves its input across shards
+                assert_eq!(codec.name(), "novelpoly");
+                continue;
+            };
+            let square = square_codec.extend(&shares).unwrap();
+            assert_eq!(square.original(), shares, "{}", codec.name());
+
+            // Only the parity quadrant: columns first, then rows
+            let quadrant = (0..2 * k)
+                .flat_map(|row| (0..2 * k).map(move |column| (row, column)))
+                .filter(|&(row, column)| row < k || column < k);
+            let repair = square_codec.repair(&square.withhold(quadrant)).unwrap();
+            assert_eq!(repair.square, square, "{}", codec.name());
+            assert!(repair.rounds >= 1);
+
+            // Scattered losses that need several rounds
+            let mut positions: Vec<_> =
+                (0..4 * k * k).map(|i| (i / (2 * k), i % (2 * k))).collect();
+            Rng::new(2).shuffle(&mut positions);
+            let repair = square_codec
+                .repair(&square.withhold(positions[..k * k].iter().copied()))
+                .unwrap();
+            assert_eq!(repair.square, square, "{}", codec.name());
+
+            // A (k+1)x(k+1) hole leaves every line through it short
+            let hole = (0..=k).flat_map(|row| (0..=k).map(move |column| (row, column)));
+            assert!(square_codec.repair(&square.withhold(hole)).is_err());
+        }
+    }
+
+    #[test]
+    fn rejects_unusable_backends_and_shapes() {
+        for codec in backends() {
+            let caps = codec.capabilities();
+            if caps.shard_alignment > 1 {
+                assert!(!supports(codec.as_ref(), 4, caps.shard_alignment + 1));
+            }
+            assert!(!supports(codec.as_ref(), caps.max_total_shards, SHARE_SIZE));
+            if let Ok(square_codec) = SquareCodec::new(codec.as_ref(), 4, SHARE_SIZE) {
+                assert!(square_codec.extend(&original(3)).is_err());
+                assert!(square_codec.repair(&vec![None; 3]).is_err());
+            }
+        }
+    }
+}
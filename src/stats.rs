@@ -0,0 +1,130 @@
+//! Latency distributions built from individually recorded samples.
+
+use std::time::Duration;
+
+/// Percentiles reported for every distribution.
+pub const PERCENTILES: &[f64] = &[50.0, 90.0, 99.0, 99.9];
+
+/// Every recorded sample of one benchmark, sorted ascending.
+#[derive(Debug, Clone)]
+pub struct Distribution {
+    samples: Vec<Duration>,
+}
+
+/// One histogram bucket covering `[lower, upper)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bucket {
+    pub lower: Duration,
+    pub upper: Duration,
+    pub count: usize,
+}
+
+impl Distribution {
+    pub fn new(mut samples: Vec<Duration>) -> Self {
+        assert!(
+            !samples.is_empty(),
+            "a distribution needs at least one sample"
+        );
+        samples.sort_unstable();
+        Self { samples }
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    pub fn min(&self) -> Duration {
+        self.samples[0]
+    }
+
+    pub fn max(&self) -> Duration {
+        self.samples[self.samples.len() - 1]
+    }
+
+    pub fn mean(&self) -> Duration {
+        self.samples.iter().sum::<Duration>() / self.samples.len() as u32
+    }
+
+    /// Nearest-rank percentile, `p` in `0.0..=100.0`.
+    pub fn percentile(&self, p: f64) -> Duration {
+        // The epsilon keeps e.g. 99.9% of 1000 at rank 999 despite rounding error
+        let rank = (p / 100.0 * self.samples.len() as f64 - 1e-9).ceil() as usize;
+        self.samples[rank.clamp(1, self.samples.len()) - 1]
+    }
+
+    /// Power-of-two buckets in nanoseconds, from the fastest to the slowest sample.
+    pub fn histogram(&self) -> Vec<Bucket> {
+        let bucket_of = |sample: &Duration| 127 - (sample.as_nanos().max(1)).leading_zeros();
+        let first = bucket_of(&self.min());
+        let last = bucket_of(&self.max());
+
+        let mut buckets: Vec<Bucket> = (first..=last)
+            .map(|exp| Bucket {
+                lower: Duration::from_nanos(1 << exp),
+                upper: Duration::from_nanos(1 << (exp + 1)),
+                count: 0,
+            })
+            .collect();
+        for sample in &self.samples {
+            buckets[(bucket_of(sample) - first) as usize].count += 1;
+        }
+        buckets
+    }
+}
+
+/// Format a duration the way divan does (e.g. `74.5 µs`).
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos() as f64;
+    let (value, unit) = if nanos >= 1e9 {
+        (nanos / 1e9, "s")
+    } else if nanos >= 1e6 {
+        (nanos / 1e6, "ms")
+    } else if nanos >= 1e3 {
+        (nanos / 1e3, "µs")
+    } else {
+        (nanos, "ns")
+    };
+    let formatted = format!("{:.4}", value);
+    let trimmed = formatted[..5.min(formatted.len())].trim_end_matches('0');
+    format!("{} {}", trimmed.trim_end_matches('.'), unit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn micros(values: &[u64]) -> Distribution {
+        Distribution::new(values.iter().map(|&v| Duration::from_micros(v)).collect())
+    }
+
+    #[test]
+    fn nearest_rank_percentiles() {
+        let dist = micros(&(1..=1000).rev().collect::<Vec<_>>());
+        assert_eq!(dist.percentile(50.0), Duration::from_micros(500));
+        assert_eq!(dist.percentile(99.0), Duration::from_micros(990));
+        assert_eq!(dist.percentile(99.9), Duration::from_micros(999));
+        assert_eq!(dist.percentile(100.0), dist.max());
+        assert_eq!(dist.percentile(0.0), dist.min());
+    }
+
+    #[test]
+    fn histogram_covers_every_sample() {
+        let dist = micros(&[1, 2, 3, 70, 75, 400]);
+        let buckets = dist.histogram();
+        assert_eq!(buckets.iter().map(|b| b.count).sum::<usize>(), dist.len());
+        assert!(buckets[0].lower <= dist.min());
+        assert!(buckets.last().unwrap().upper > dist.max());
+    }
+
+    #[test]
+    fn divan_style_durations() {
+        assert_eq!(format_duration(Duration::from_nanos(74_500)), "74.5 µs");
+        assert_eq!(format_duration(Duration::from_nanos(1_234_000)), "1.234 ms");
+        assert_eq!(format_duration(Duration::from_nanos(408_300)), "408.3 µs");
+        assert_eq!(format_duration(Duration::from_nanos(999)), "999 ns");
+    }
+}
@@ -0,0 +1,173 @@
+//! Timestamp-counter cycles, so timings compare across machines with different clocks.
+//!
+//! The TSC ticks at a fixed reference rate on current x86 CPUs. Calibration
+//! measures that rate against wall time, and times a chain of dependent adds,
+//! one core cycle each, to see what the cores actually ran at. When the core
+//! clock moves between rounds, frequency scaling is active and cycle counts
+//! are only as stable as the governor.
+
+use std::time::{Duration, Instant};
+
+/// Wall time per calibration round.
+const ROUND: Duration = Duration::from_millis(20);
+
+/// Calibration rounds; the median rate is kept.
+const ROUNDS: usize = 5;
+
+/// Iterations of the spin loop, 8 dependent adds each.
+const SPIN_ITERATIONS: u64 = 1 << 20;
+
+/// Spread of the core clock across rounds above which scaling is reported.
+const SCALING_TOLERANCE: f64 = 0.05;
+
+/// Current TSC value, or `None` on CPUs without one.
+#[inline]
+pub fn read() -> Option<u64> {
+    #[cfg(target_arch = "x86_64")]
+    {
+        // The fence keeps earlier instructions from retiring after the read
+        // SAFETY: lfence and rdtsc are part of the x86_64 baseline
+        unsafe {
+            std::arch::x86_64::_mm_lfence();
+            Some(std::arch::x86_64::_rdtsc())
+        }
+    }
+    #[cfg(not(target_arch = "x86_64"))]
+    {
+        None
+    }
+}
+
+/// TSC rate and the core clock seen while calibrating.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Calibration {
+    /// TSC ticks per second of wall time.
+    pub tsc_hz: f64,
+    /// Whether the CPU promises a constant TSC rate across P- and C-states.
+    pub invariant: bool,
+    /// Core clock of each spin round, in Hz.
+    pub core_hz: Vec<f64>,
+}
+
+impl Calibration {
+    /// Calibrates against [`Instant`]; `None` without a TSC. Takes about 0.1s.
+    pub fn measure() -> Option<Self> {
+        let mut rates = Vec::with_capacity(ROUNDS);
+        let mut core_hz = Vec::with_capacity(ROUNDS);
+        for _ in 0..ROUNDS {
+            let (start, ticks) = (Instant::now(), read()?);
+            while start.elapsed() < ROUND {}
+            let (elapsed, ticks) = (start.elapsed(), read()? - ticks);
+            rates.push(ticks as f64 / elapsed.as_secs_f64());
+
+            // Idle between rounds, so a governor that clocks down shows up
+            std::thread::sleep(ROUND);
+            let start = Instant::now();
+            let cycles = spin(SPIN_ITERATIONS)?;
+            core_hz.push(cycles as f64 / start.elapsed().as_secs_f64());
+        }
+        rates.sort_by(f64::total_cmp);
+        Some(Self {
+            tsc_hz: rates[ROUNDS / 2],
+            invariant: invariant_tsc(),
+            core_hz,
+        })
+    }
+
+    /// Slowest and fastest core clock across the rounds, in Hz.
+    pub fn core_range(&self) -> (f64, f64) {
+        let min = self.core_hz.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = self.core_hz.iter().copied().fold(0.0, f64::max);
+        (min, max)
+    }
+
+    /// Whether the core clock moved by more than 5% between rounds.
+    pub fn frequency_scaling(&self) -> bool {
+        let (min, max) = self.core_range();
+        max > min * (1.0 + SCALING_TOLERANCE)
+    }
+
+    /// Why cycle counts from this machine may not be comparable, if they may not.
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if !self.invariant {
+            warnings.push("TSC is not invariant; its rate follows the core clock".to_string());
+        }
+        if self.frequency_scaling() {
+            let (min, max) = self.core_range();
+            warnings.push(format!(
+                "frequency scaling: core clock ranged {:.2}-{:.2} GHz while calibrating",
+                min / 1e9,
+                max / 1e9
+            ));
+        }
+        warnings
+    }
+}
+
+/// Core cycles spent running `iterations` of 8 dependent adds.
+///
+/// Assumes a register add has one cycle of latency, which holds on x86_64
+/// cores from both vendors but is not architectural. On a core where it does
+/// not, the reported core clock is off by the add latency. The scaling check
+/// compares rounds with each other, so it is unaffected.
+#[cfg(target_arch = "x86_64")]
+fn spin(iterations: u64) -> Option<u64> {
+    let mut value = 0u64;
+    // SAFETY: only touches the two registers bound below
+    unsafe {
+        std::arch::asm!(
+            "2:",
+            "add {value}, 1",
+            "add {value}, 1",
+            "add {value}, 1",
+            "add {value}, 1",
+            "add {value}, 1",
+            "add {value}, 1",
+            "add {value}, 1",
+            "add {value}, 1",
+            "dec {remaining}",
+            "jnz 2b",
+            value = inout(reg) value,
+            remaining = inout(reg) iterations => _,
+            options(nomem, nostack),
+        );
+    }
+    // Each add waits on the previous one, so the chain takes one add latency per add
+    Some(std::hint::black_box(value))
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+fn spin(_iterations: u64) -> Option<u64> {
+    None
+}
+
+/// CPUID's invariant-TSC flag (leaf 0x8000_0007, EDX bit 8).
+#[cfg(target_arch = "x86_64")]
+fn invariant_tsc() -> bool {
+    use std::arch::x86_64::__cpuid;
+    __cpuid(0x8000_0000).eax >= 0x8000_0007 && __cpuid(0x8000_0007).edx & (1 << 8) != 0
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+fn invariant_tsc() -> bool {
+    false
+}
+
+#[cfg(all(test, target_arch = "x86_64"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn calibration_matches_wall_clock() {
+        let calibration = Calibration::measure().expect("x86_64 has a TSC");
+        // Anything from a throttled VM to a fast desktop
+        assert!(
+            (1e8..1e11).contains(&calibration.tsc_hz),
+            "{:?}",
+            calibration
+        );
+        assert_eq!(calibration.core_hz.len(), ROUNDS);
+        assert!(calibration.core_hz.iter().all(|&hz| hz > 1e7));
+    }
+}